@@ -1,10 +1,14 @@
 mod components;
+mod diagnostic;
+mod error;
 mod utilities;
 
 use components::document::*;
 use components::item::*;
 use components::container::*;
 use components::table::*;
+use components::metadata::*;
+use components::contents::*;
 use std::fs::File;
 
 const DOCUMENT_NAME: &str = "generated_tex/main.tex";
@@ -38,17 +42,17 @@ fn main()  {
 
     doc.add_packages(packages);
 
+    doc.set_title("Title");
+    doc.add_authors(vec![Author::new("Author", "", "")]);
+    doc.set_date(DocumentDate::Custom(String::from("YYYY / MM / DD")));
+
     let commands = vec![
-        Command::new(r"\title{Title}"),
-        Command::new(r"\author{Author}"),
-        Command::new(r"\date{YYYY / MM / DD}"),
         Command::new(r"\setlength{\tabcolsep}{18pt}")
     ];
 
     doc.add_global_commands(commands);
 
-    doc.add_item(Command::new(r"\maketitle"));
-    doc.add_item(Command::new(r"\tableofcontents"));
+    doc.add_item(TableOfContents::new());
 
     let mut chapter_1 = Chapter::new(
         "Lorem Ipsum",
@@ -116,7 +120,7 @@ fn main()  {
 
     table_1.add_component(HorizontalLine::new());
 
-    let table_row_1 = TableRow::new(vec![Text::new(r"\multicolumn{3}{|c|}{**Lorem Ipsum**}")]);
+    let table_row_1 = TableRow::new(vec![Text::raw(r"\multicolumn{3}{|c|}{\textbf{Lorem Ipsum}}")]);
 
     table_1.add_component(table_row_1);
     table_1.add_component(HorizontalLine::new());
@@ -145,5 +149,5 @@ fn main()  {
 
     doc.add_item(chapter_1);
 
-    doc.build();
+    doc.build().unwrap();
 }
@@ -0,0 +1,251 @@
+//! # RusTeX derive
+//!
+//! Derive macro of the `LatexTable` trait of [RusTeX](https://github.com/zeckrust/RusTeX),
+//! enabled by its `derive` feature.
+//! Refer to the documentation of `LatexTable` for more information.
+
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Error, Fields, LitInt, LitStr};
+
+/// The options of a field, set with `#[latex(...)]`.
+struct Column {
+    member: syn::Ident,
+    header: String,
+    alignment: Option<TokenStream2>,
+    format: Option<TokenStream2>,
+    order: Option<LitInt>
+}
+
+/// Derives `LatexTable` for a struct with named fields.
+///
+/// Every field becomes a column, and must implement `Display`.
+/// The options of the `#[latex(...)]` field attribute are listed in the documentation
+/// of the `LatexTable` trait of `rustex`.
+#[proc_macro_derive(LatexTable, attributes(latex))]
+pub fn derive_latex_table(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    match expand_latex_table(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into()
+    }
+}
+
+fn expand_latex_table(input: &DeriveInput) -> Result<TokenStream2, Error> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => return Err(Error::new_spanned(&input.ident, "LatexTable can only be derived for structs with named fields"))
+        },
+        _ => return Err(Error::new_spanned(&input.ident, "LatexTable can only be derived for structs"))
+    };
+
+    let mut columns: Vec<Column> = Vec::new();
+
+    for field in fields {
+        if let Some(column) = parse_column(field)? {
+            columns.push(column);
+        }
+    }
+
+    let columns = arrange_columns(columns)?;
+
+    let headers = columns.iter().map(|column| &column.header);
+    let alignments = columns.iter().map(|column| match (&column.alignment, &column.format) {
+        (Some(alignment), _) => alignment.clone(),
+        (None, Some(_)) => quote!(::rustex::CellAlignment::Right),
+        (None, None) => quote!(::rustex::CellAlignment::Left)
+    });
+    let cells = columns.iter().map(|column| {
+        let member = &column.member;
+
+        match &column.format {
+            Some(format) => quote!(::rustex::format_cell(&self.#member, ::std::option::Option::Some(#format))),
+            None => quote!(::rustex::format_cell(&self.#member, ::std::option::Option::None))
+        }
+    });
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics ::rustex::LatexTable for #name #type_generics #where_clause {
+            fn get_headers() -> ::std::vec::Vec<::std::string::String> {
+                ::std::vec![#(::std::string::String::from(#headers)),*]
+            }
+
+            fn get_alignments() -> ::std::vec::Vec<::rustex::CellAlignment> {
+                ::std::vec![#(#alignments),*]
+            }
+
+            fn get_cells(&self) -> ::std::vec::Vec<::rustex::Text> {
+                ::std::vec![#(#cells),*]
+            }
+        }
+    })
+}
+
+fn parse_column(field: &syn::Field) -> Result<Option<Column>, Error> {
+    let member = field.ident.clone().ok_or_else(|| Error::new(Span::call_site(), "expected a named field"))?;
+    let mut column = Column {
+        header: member.to_string(),
+        member,
+        alignment: None,
+        format: None,
+        order: None
+    };
+    let mut skip = false;
+
+    for attribute in field.attrs.iter().filter(|attribute| attribute.path().is_ident("latex")) {
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("header") {
+                column.header = meta.value()?.parse::<LitStr>()?.value();
+            } else if meta.path.is_ident("align") {
+                let alignment: LitStr = meta.value()?.parse()?;
+                column.alignment = Some(parse_alignment(&alignment)?);
+            } else if meta.path.is_ident("format") {
+                let format: LitStr = meta.value()?.parse()?;
+                column.format = Some(parse_format(&format)?);
+            } else if meta.path.is_ident("order") {
+                let order: LitInt = meta.value()?.parse()?;
+                order.base10_parse::<usize>()?;
+                column.order = Some(order);
+            } else if meta.path.is_ident("skip") {
+                skip = true;
+            } else {
+                return Err(meta.error("expected `header`, `align`, `format`, `order` or `skip`"));
+            }
+
+            Ok(())
+        })?;
+    }
+
+    Ok((!skip).then_some(column))
+}
+
+// Columns with an `order` are placed at their position,
+// and the other columns fill the remaining positions in the order of their fields
+fn arrange_columns(columns: Vec<Column>) -> Result<Vec<Column>, Error> {
+    let column_count = columns.len();
+    let mut positions: Vec<Option<Column>> = (0..column_count).map(|_| None).collect();
+    let mut unordered: Vec<Column> = Vec::new();
+
+    for column in columns {
+        let order = match &column.order {
+            Some(order) => order,
+            None => {
+                unordered.push(column);
+                continue;
+            }
+        };
+
+        let position: usize = order.base10_parse()?;
+
+        match positions.get(position) {
+            None => return Err(Error::new_spanned(order, format!(
+                "order {} is out of range, the table has {} column(s) numbered from 0", position, column_count
+            ))),
+            Some(Some(_)) => return Err(Error::new_spanned(order, format!(
+                "order {} is already used by another field", position
+            ))),
+            Some(None) => positions[position] = Some(column)
+        }
+    }
+
+    let mut unordered = unordered.into_iter();
+
+    Ok(positions
+        .into_iter()
+        .map(|column| column.or_else(|| unordered.next()).unwrap())
+        .collect())
+}
+
+fn parse_alignment(alignment: &LitStr) -> Result<TokenStream2, Error> {
+    match alignment.value().as_str() {
+        "left" => Ok(quote!(::rustex::CellAlignment::Left)),
+        "center" => Ok(quote!(::rustex::CellAlignment::Center)),
+        "right" => Ok(quote!(::rustex::CellAlignment::Right)),
+        _ => Err(Error::new_spanned(alignment, "expected \"left\", \"center\" or \"right\""))
+    }
+}
+
+// A format is written as a call, e.g. `decimals(2)`
+fn parse_format(format: &LitStr) -> Result<TokenStream2, Error> {
+    let value = format.value();
+    let error = || Error::new_spanned(format, "expected \"decimals(n)\", \"thousands(n)\", \"percent(n)\" or \"scientific(n)\"");
+
+    let (name, argument) = value.trim().strip_suffix(')').and_then(|call| call.split_once('(')).ok_or_else(error)?;
+    let decimals: usize = argument.trim().parse().map_err(|_| error())?;
+
+    match name.trim() {
+        "decimals" => Ok(quote!(::rustex::NumberFormat::Decimals(#decimals))),
+        "thousands" => Ok(quote!(::rustex::NumberFormat::Thousands(#decimals))),
+        "percent" => Ok(quote!(::rustex::NumberFormat::Percent(#decimals))),
+        "scientific" => Ok(quote!(::rustex::NumberFormat::Scientific(#decimals))),
+        _ => Err(error())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use syn::parse_quote;
+    use super::*;
+
+    fn arranged_headers(input: DeriveInput) -> Result<Vec<String>, String> {
+        let fields = match &input.data {
+            Data::Struct(data) => &data.fields,
+            _ => panic!("expected a struct")
+        };
+
+        let columns: Vec<Column> = fields
+            .iter()
+            .filter_map(|field| parse_column(field).unwrap())
+            .collect();
+
+        arrange_columns(columns)
+            .map(|columns| columns.into_iter().map(|column| column.header).collect())
+            .map_err(|error| error.to_string())
+    }
+
+    #[test]
+    fn keeps_the_order_of_the_fields_by_default() {
+        let input: DeriveInput = parse_quote! {
+            struct Record { a: u32, #[latex(skip)] b: u32, c: u32 }
+        };
+
+        assert_eq!(arranged_headers(input).unwrap(), vec!["a", "c"]);
+    }
+
+    #[test]
+    fn fills_the_remaining_positions_in_field_order() {
+        let input: DeriveInput = parse_quote! {
+            struct Record {
+                a: u32,
+                b: u32,
+                #[latex(order = 0)]
+                c: u32,
+                #[latex(order = 2)]
+                d: u32,
+                e: u32
+            }
+        };
+
+        assert_eq!(arranged_headers(input).unwrap(), vec!["c", "a", "d", "b", "e"]);
+    }
+
+    #[test]
+    fn rejects_conflicting_orders() {
+        let input: DeriveInput = parse_quote! {
+            struct Record { #[latex(order = 1)] a: u32, #[latex(order = 1)] b: u32 }
+        };
+        assert_eq!(arranged_headers(input).unwrap_err(), "order 1 is already used by another field");
+
+        let input: DeriveInput = parse_quote! {
+            struct Record { #[latex(order = 2)] a: u32, b: u32 }
+        };
+        assert_eq!(arranged_headers(input).unwrap_err(),
+                   "order 2 is out of range, the table has 2 column(s) numbered from 0");
+    }
+}
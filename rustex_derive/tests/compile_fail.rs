@@ -0,0 +1,5 @@
+#[test]
+fn reports_invalid_derives() {
+    let cases = trybuild::TestCases::new();
+    cases.compile_fail("tests/ui/*.rs");
+}
@@ -0,0 +1,11 @@
+use rustex::LatexTable;
+
+#[derive(LatexTable)]
+struct Record {
+    #[latex(order = 1)]
+    name: String,
+    #[latex(order = 1)]
+    mass: f64
+}
+
+fn main() {}
@@ -0,0 +1,8 @@
+use rustex::LatexTable;
+
+#[derive(LatexTable)]
+enum Record {
+    Name(String)
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use rustex::LatexTable;
+
+#[derive(LatexTable)]
+struct Record {
+    #[latex(align = "middle")]
+    name: String
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use rustex::LatexTable;
+
+#[derive(LatexTable)]
+struct Record {
+    #[latex(format = "decimals(two)")]
+    mass: f64
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use rustex::LatexTable;
+
+#[derive(LatexTable)]
+struct Record(String, f64);
+
+fn main() {}
@@ -0,0 +1,9 @@
+use rustex::LatexTable;
+
+#[derive(LatexTable)]
+struct Record {
+    #[latex(title = "Name")]
+    name: String
+}
+
+fn main() {}
@@ -0,0 +1,543 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use crate::components::document::Document;
+use crate::diagnostic::*;
+use crate::error::RustexError;
+
+const DEFAULT_MAX_RUNS: usize = 5;
+pub(crate) const IMAGE_FORMATS: [&str; 5] = ["png", "jpg", "jpeg", "pdf", "eps"];
+// e.g. `Rerun to get cross-references right` or `Rerun to get outlines right`
+const RERUN_MESSAGE: &str = "Rerun to get";
+// The commands of the .aux file that are only read back by the next run
+const AUX_REFERENCE_COMMANDS: [&str; 3] = [r"\newlabel", r"\@writefile", r"\bibcite"];
+
+/// A LaTeX engine installed on the computer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Engine {
+    PdfLatex,
+    XeLatex,
+    LuaLatex,
+    Latexmk,
+    Tectonic
+}
+
+impl Engine {
+    /// Every `Engine`, by order of preference.
+    pub const ALL: [Engine; 5] = [
+        Engine::Latexmk,
+        Engine::PdfLatex,
+        Engine::XeLatex,
+        Engine::LuaLatex,
+        Engine::Tectonic
+    ];
+
+    /// Finds the first `Engine` installed on the `PATH`, by order of preference.
+    pub fn find() -> Option<Engine> {
+        Engine::ALL.into_iter().find(|engine| engine.is_installed())
+    }
+
+    /// Checks whether the `Engine` is installed on the `PATH`.
+    pub fn is_installed(&self) -> bool {
+        find_executable(self.get_command()).is_some()
+    }
+
+    pub fn get_command(&self) -> &str {
+        match self {
+            Engine::PdfLatex => "pdflatex",
+            Engine::XeLatex => "xelatex",
+            Engine::LuaLatex => "lualatex",
+            Engine::Latexmk => "latexmk",
+            Engine::Tectonic => "tectonic"
+        }
+    }
+
+    /// Gets the extensions of the image formats the `Engine` can include.
+    /// `eps` images are converted to PDF by `epstopdf`, which `tectonic` does not run.
+    pub fn get_image_formats(&self) -> &[&str] {
+        match self {
+            Engine::Tectonic => &IMAGE_FORMATS[..4],
+            _ => &IMAGE_FORMATS
+        }
+    }
+
+    // `latexmk` and `tectonic` rerun the compilation by themselves until references are resolved
+    fn handles_reruns(&self) -> bool {
+        matches!(self, Engine::Latexmk | Engine::Tectonic)
+    }
+
+    fn get_args(&self, tex_path: &Path, output_dir: &Path, shell_escape: bool) -> Vec<String> {
+        let tex_path = tex_path.to_string_lossy().into_owned();
+        let output_dir = output_dir.to_string_lossy().into_owned();
+
+        let mut args = match shell_escape {
+            true => self.get_shell_escape_args(),
+            false => Vec::new()
+        };
+
+        args.extend(match self {
+            Engine::PdfLatex | Engine::XeLatex | Engine::LuaLatex => vec![
+                String::from("-interaction=nonstopmode"),
+                String::from("-halt-on-error"),
+                format!("-output-directory={}", output_dir),
+                tex_path
+            ],
+            Engine::Latexmk => vec![
+                String::from("-pdf"),
+                String::from("-interaction=nonstopmode"),
+                String::from("-halt-on-error"),
+                format!("-output-directory={}", output_dir),
+                tex_path
+            ],
+            Engine::Tectonic => vec![
+                String::from("--keep-logs"),
+                String::from("--outdir"),
+                output_dir,
+                tex_path
+            ]
+        });
+
+        args
+    }
+
+    fn get_shell_escape_args(&self) -> Vec<String> {
+        match self {
+            Engine::Tectonic => vec![String::from("-Z"), String::from("shell-escape")],
+            _ => vec![String::from("-shell-escape")]
+        }
+    }
+}
+
+/// Compiles generated LaTeX files to PDF with a local `Engine`.
+/// The engine is run from the directory of the LaTeX file, so relative image paths are preserved,
+/// and is rerun until cross-references are stable.
+/// A single run is enough when the .aux file holds no cross-references, or when it is left unchanged
+/// by a run, e.g. when the same document is compiled again.
+///
+/// `latexmk` and `tectonic` rerun by themselves. `tectonic` keeps its .log file beside the PDF
+/// for the diagnostics, but not its intermediate files (.aux, .toc, etc.).
+///
+/// Example:
+///
+/// ```no_run
+/// # use rustex::*;
+/// # use std::path::Path;
+/// let doc_class = DocumentClass::new(ClassType::Article, vec![]);
+/// let mut doc = Document::from_writer(Vec::new(), doc_class);
+/// doc.add_item(Text::new("Hello **world**"));
+///
+/// let compiler = Compiler::find().unwrap();
+/// let pdf_path = compiler.compile_document(&mut doc, Path::new("generated_tex"), "main").unwrap();
+/// ```
+pub struct Compiler {
+    engine: Engine,
+    output_dir: Option<PathBuf>,
+    max_runs: usize,
+    shell_escape: bool
+}
+
+impl Compiler {
+    /// Initializes a new `Compiler` object
+    pub fn new(_engine: Engine) -> Self {
+        Self {
+            engine: _engine,
+            output_dir: None,
+            max_runs: DEFAULT_MAX_RUNS,
+            shell_escape: false
+        }
+    }
+
+    /// Initializes a new `Compiler` object with the first `Engine` installed on the `PATH`.
+    pub fn find() -> Result<Self, RustexError> {
+        match Engine::find() {
+            Some(engine) => Ok(Self::new(engine)),
+            None => {
+                let commands: Vec<&str> = Engine::ALL.iter().map(|engine| engine.get_command()).collect();
+                Err(RustexError::compile(
+                    &format!("no LaTeX engine found on the PATH (looked for {})", commands.join(", ")),
+                    ""
+                ))
+            }
+        }
+    }
+
+    /// Sets the directory of the generated PDF and auxiliary files.
+    /// Defaults to the directory of the LaTeX file.
+    pub fn set_output_dir(&mut self, output_dir: &Path) {
+        self.output_dir = Some(output_dir.to_path_buf());
+    }
+
+    /// Sets the maximum number of runs of the `Engine` to resolve cross-references.
+    pub fn set_max_runs(&mut self, max_runs: usize) {
+        self.max_runs = max_runs.max(1);
+    }
+
+    /// Allows the `Engine` to run external programs, as needed by the `minted` backend of `CodeListing`.
+    /// Only enable it for trusted documents.
+    pub fn set_shell_escape(&mut self, shell_escape: bool) {
+        self.shell_escape = shell_escape;
+    }
+
+    pub fn get_engine(&self) -> Engine {
+        self.engine
+    }
+
+    /// Compiles a LaTeX file and returns the path of the generated PDF.
+    pub fn compile(&self, tex_path: &Path) -> Result<PathBuf, RustexError> {
+        let tex_path = fs::canonicalize(tex_path)?;
+        let tex_dir = tex_path.parent().unwrap_or(Path::new(".")).to_path_buf();
+        let output_dir = match &self.output_dir {
+            Some(output_dir) => output_dir.clone(),
+            None => tex_dir.clone()
+        };
+
+        fs::create_dir_all(&output_dir)?;
+        let output_dir = fs::canonicalize(output_dir)?;
+        let job_name = tex_path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+        let aux_path = output_dir.join(format!("{}.aux", job_name));
+        let log_path = output_dir.join(format!("{}.log", job_name));
+
+        let runs = match self.engine.handles_reruns() {
+            true => 1,
+            false => self.max_runs
+        };
+
+        let mut previous_aux: Option<Vec<u8>> = fs::read(&aux_path).ok();
+
+        for _ in 0..runs {
+            self.run_engine(&tex_path, &tex_dir, &output_dir, &log_path)?;
+
+            let aux = fs::read(&aux_path).ok();
+            let log = fs::read_to_string(&log_path).unwrap_or_default();
+
+            if !needs_rerun(&log, aux.as_deref(), previous_aux.as_deref()) {
+                break;
+            }
+
+            previous_aux = aux;
+        }
+
+        let pdf_path = output_dir.join(format!("{}.pdf", job_name));
+
+        match pdf_path.is_file() {
+            true => Ok(pdf_path),
+            false => Err(RustexError::compile(
+                &format!("{} did not generate {}", self.engine.get_command(), pdf_path.display()),
+                &fs::read_to_string(&log_path).unwrap_or_default()
+            ))
+        }
+    }
+
+    /// Builds a `Document` into `<directory>/<job_name>.tex`, compiles it
+    /// and returns the path of the generated PDF.
+    /// Compilation errors carry the path of the `Item` that wrote the failing line.
+    pub fn compile_document(&self, doc: &mut Document, directory: &Path, job_name: &str) -> Result<PathBuf, RustexError> {
+        fs::create_dir_all(directory)?;
+        let tex_path = directory.join(format!("{}.tex", job_name));
+        fs::write(&tex_path, doc.render_to_string()?)?;
+        doc.write_assets()?;
+
+        self.compile(&tex_path)
+            .map_err(|error| locate_error(error, &doc.get_source_map()))
+    }
+
+    fn run_engine(&self, tex_path: &Path, tex_dir: &Path, output_dir: &Path, log_path: &Path) -> Result<(), RustexError> {
+        let output = Command::new(self.engine.get_command())
+            .args(self.engine.get_args(tex_path, output_dir, self.shell_escape))
+            .current_dir(tex_dir)
+            .output()
+            .map_err(|error| match error.kind() {
+                io::ErrorKind::NotFound => RustexError::compile(
+                    &format!("{} was not found on the PATH", self.engine.get_command()), ""
+                ),
+                _ => RustexError::from(error)
+            })?;
+
+        if output.status.success() {
+            return Ok(());
+        }
+
+        let log = match fs::read_to_string(log_path) {
+            Ok(log) => log,
+            Err(_) => String::from_utf8_lossy(&output.stdout).into_owned()
+        };
+
+        Err(RustexError::compile(
+            &format!("{} failed ({})", self.engine.get_command(), output.status),
+            &log
+        ))
+    }
+}
+
+// A run is needed when LaTeX asks for it, or when the cross-references written by the last run
+// differ from the ones it read
+fn needs_rerun(log: &str, aux: Option<&[u8]>, previous_aux: Option<&[u8]>) -> bool {
+    if log.contains(RERUN_MESSAGE) {
+        return true;
+    }
+
+    match (aux, previous_aux) {
+        (Some(aux), Some(previous_aux)) => aux != previous_aux,
+        (Some(aux), None) => {
+            let aux = String::from_utf8_lossy(aux);
+            AUX_REFERENCE_COMMANDS.iter().any(|command| aux.contains(command))
+        }
+        (None, _) => false
+    }
+}
+
+// Points a compilation error at the `Item` that wrote the line of the first error of the log
+fn locate_error(error: RustexError, source_map: &SourceMap) -> RustexError {
+    let RustexError::Compile { message, log, .. } = &error else {
+        return error;
+    };
+
+    let first_error = parse_log(log, source_map)
+        .into_iter()
+        .find(|diagnostic| diagnostic.is_error());
+
+    match first_error {
+        Some(diagnostic) => {
+            let mut located_error = RustexError::compile(&format!("{}: {}", message, diagnostic.get_message()), log);
+
+            if let Some(item) = diagnostic.get_item() {
+                for description in item.get_path_segments().iter().rev() {
+                    located_error = located_error.within(description.clone());
+                }
+            }
+
+            located_error
+        }
+        None => error
+    }
+}
+
+fn find_executable(name: &str) -> Option<PathBuf> {
+    let paths = env::var_os("PATH")?;
+
+    env::split_paths(&paths)
+        .flat_map(|dir| {
+            let executable = dir.join(name);
+            vec![executable.with_extension(env::consts::EXE_EXTENSION), executable]
+        })
+        .find(|executable| executable.is_file())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use std::os::unix::fs::PermissionsExt;
+    use std::sync::Mutex;
+    use crate::components::document::*;
+    use crate::components::item::*;
+    use crate::components::container::*;
+
+    // The fake engines are found through the `PATH`, which is shared by every test
+    static PATH_LOCK: Mutex<()> = Mutex::new(());
+
+    // Reads the arguments like pdflatex and counts its runs in `<output_dir>/runs`
+    const SCRIPT_PROLOGUE: &str = r#"#!/bin/sh
+for arg in "$@"; do
+    case "$arg" in
+        -output-directory=*) out="${arg#-output-directory=}" ;;
+    esac
+    tex="$arg"
+done
+job=$(basename "$tex" .tex)
+run=$(( $(cat "$out/runs" 2>/dev/null || echo 0) + 1 ))
+echo "$run" > "$out/runs"
+"#;
+
+    struct FakeEngine {
+        directory: PathBuf,
+        original_path: Option<std::ffi::OsString>
+    }
+
+    impl FakeEngine {
+        // Installs a fake `pdflatex` running `body` after the prologue, at the front of the `PATH`
+        fn install(name: &str, body: &str) -> Self {
+            let directory = env::temp_dir().join(format!("rustex_compile_{}_{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&directory);
+            fs::create_dir_all(directory.join("bin")).unwrap();
+
+            let script_path = directory.join("bin").join("pdflatex");
+            fs::write(&script_path, format!("{}{}", SCRIPT_PROLOGUE, body)).unwrap();
+            fs::set_permissions(&script_path, fs::Permissions::from_mode(0o755)).unwrap();
+
+            let original_path = env::var_os("PATH");
+            let mut paths = vec![directory.join("bin")];
+            paths.extend(original_path.iter().flat_map(env::split_paths));
+            env::set_var("PATH", env::join_paths(paths).unwrap());
+
+            Self { directory, original_path }
+        }
+
+        fn write_tex(&self) -> PathBuf {
+            let tex_path = self.directory.join("main.tex");
+            fs::write(&tex_path, "\\documentclass{article}\n").unwrap();
+            tex_path
+        }
+
+        fn get_runs(&self) -> usize {
+            fs::read_to_string(self.directory.join("runs")).unwrap().trim().parse().unwrap()
+        }
+    }
+
+    impl Drop for FakeEngine {
+        fn drop(&mut self) {
+            match &self.original_path {
+                Some(path) => env::set_var("PATH", path),
+                None => env::remove_var("PATH")
+            }
+
+            let _ = fs::remove_dir_all(&self.directory);
+        }
+    }
+
+    fn lock_path() -> std::sync::MutexGuard<'static, ()> {
+        PATH_LOCK.lock().unwrap_or_else(|error| error.into_inner())
+    }
+
+    #[test]
+    fn runs_once_without_cross_references() {
+        let _lock = lock_path();
+        let engine = FakeEngine::install("once", r#"
+printf '%s\n' '\relax' > "$out/$job.aux"
+echo "Output written on $job.pdf" > "$out/$job.log"
+echo "pdf" > "$out/$job.pdf"
+"#);
+
+        let pdf_path = Compiler::new(Engine::PdfLatex).compile(&engine.write_tex()).unwrap();
+
+        assert_eq!(pdf_path, fs::canonicalize(&engine.directory).unwrap().join("main.pdf"));
+        assert_eq!(engine.get_runs(), 1);
+    }
+
+    #[test]
+    fn stops_when_aux_is_stable() {
+        let _lock = lock_path();
+        let engine = FakeEngine::install("stable", r#"
+printf '%s\n' '\newlabel{sec_1}{{1}{1}}' > "$out/$job.aux"
+echo "Output written on $job.pdf" > "$out/$job.log"
+echo "pdf" > "$out/$job.pdf"
+"#);
+        let tex_path = engine.write_tex();
+
+        Compiler::new(Engine::PdfLatex).compile(&tex_path).unwrap();
+        assert_eq!(engine.get_runs(), 2);
+
+        // The .aux file of the previous compilation is already stable
+        Compiler::new(Engine::PdfLatex).compile(&tex_path).unwrap();
+        assert_eq!(engine.get_runs(), 3);
+    }
+
+    #[test]
+    fn reruns_until_aux_is_stable() {
+        let _lock = lock_path();
+        let engine = FakeEngine::install("aux", r#"
+printf '%s{%s}\n' '\newlabel' "$(( run < 3 ? run : 3 ))" > "$out/$job.aux"
+echo "Output written on $job.pdf" > "$out/$job.log"
+echo "pdf" > "$out/$job.pdf"
+"#);
+
+        Compiler::new(Engine::PdfLatex).compile(&engine.write_tex()).unwrap();
+
+        assert_eq!(engine.get_runs(), 4);
+    }
+
+    #[test]
+    fn reruns_on_rerun_messages() {
+        let _lock = lock_path();
+        let engine = FakeEngine::install("rerun", r#"
+echo "aux" > "$out/$job.aux"
+if [ "$run" -lt 3 ]; then
+    echo "LaTeX Warning: Label(s) may have changed. Rerun to get cross-references right." > "$out/$job.log"
+else
+    echo "Output written on $job.pdf" > "$out/$job.log"
+fi
+echo "pdf" > "$out/$job.pdf"
+"#);
+
+        Compiler::new(Engine::PdfLatex).compile(&engine.write_tex()).unwrap();
+
+        assert_eq!(engine.get_runs(), 3);
+    }
+
+    #[test]
+    fn stops_at_max_runs() {
+        let _lock = lock_path();
+        let engine = FakeEngine::install("max_runs", r#"
+echo "aux $run" > "$out/$job.aux"
+echo "Rerun to get cross-references right." > "$out/$job.log"
+echo "pdf" > "$out/$job.pdf"
+"#);
+
+        let mut compiler = Compiler::new(Engine::PdfLatex);
+        compiler.set_max_runs(2);
+        let pdf_path = compiler.compile(&engine.write_tex()).unwrap();
+
+        assert!(pdf_path.ends_with("main.pdf"));
+        assert_eq!(engine.get_runs(), 2);
+    }
+
+    #[test]
+    fn fails_without_pdf() {
+        let _lock = lock_path();
+        let engine = FakeEngine::install("no_pdf", r#"
+echo "No pages of output." > "$out/$job.log"
+"#);
+
+        let error = Compiler::new(Engine::PdfLatex).compile(&engine.write_tex()).unwrap_err();
+
+        assert!(matches!(&error, RustexError::Compile { log, .. } if log.contains("No pages of output.")));
+        assert!(error.to_string().contains("did not generate"));
+    }
+
+    #[test]
+    fn fails_on_missing_engine() {
+        let _lock = lock_path();
+        let engine = FakeEngine::install("missing", "");
+        let tex_path = engine.write_tex();
+        env::set_var("PATH", engine.directory.join("bin"));
+
+        let error = Compiler::new(Engine::XeLatex).compile(&tex_path).unwrap_err();
+
+        assert!(matches!(&error, RustexError::Compile { message, .. } if message == "xelatex was not found on the PATH"));
+    }
+
+    #[test]
+    fn locates_errors_in_the_source_map() {
+        let _lock = lock_path();
+
+        let mut doc = Document::from_writer(Vec::new(), DocumentClass::new(ClassType::Article, vec![]));
+        let mut section = Section::new("Data", SectionType::Section, true, "sec_data");
+        section.add_item(Text::raw(r"\undefinedmacro"));
+        doc.add_item(section);
+
+        let latex = doc.render_to_string().unwrap();
+        let line = latex.lines().position(|line| line.contains(r"\undefinedmacro")).unwrap() + 1;
+
+        let engine = FakeEngine::install("error", &format!(r#"
+printf '%s\n' '! Undefined control sequence.' 'l.{} \undefinedmacro' > "$out/$job.log"
+exit 1
+"#, line));
+
+        let error = Compiler::new(Engine::PdfLatex)
+            .compile_document(&mut doc, &engine.directory, "main")
+            .unwrap_err();
+
+        match &error {
+            RustexError::Compile { path, message, log } => {
+                assert_eq!(path, &vec![String::from("Section 'Data'"), String::from("Text")]);
+                assert!(message.starts_with("pdflatex failed"));
+                assert!(message.ends_with(": Undefined control sequence."));
+                assert!(log.contains(&format!("l.{}", line)));
+            }
+            _ => panic!("expected a compilation error, got {}", error)
+        }
+
+        assert_eq!(engine.get_runs(), 1);
+    }
+}
@@ -0,0 +1,319 @@
+use std::collections::HashMap;
+use std::collections::hash_map::DefaultHasher;
+use std::fs::{self, File};
+use std::hash::Hasher;
+use std::io::{BufReader, Read};
+use std::path::{Path, PathBuf};
+use super::graphics::resolve_image;
+use crate::compile::IMAGE_FORMATS;
+use crate::error::RustexError;
+use crate::utilities::def_syntax::*;
+
+/// Defines how the assets are put in the assets directory.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AssetMode {
+    Copy,
+    /// Falls back to a copy when the asset is on another file system.
+    HardLink
+}
+
+/// A file referenced by the `Document`, e.g. the image of a `Figure`.
+#[derive(Clone, Debug)]
+struct Asset {
+    source: PathBuf,
+    name: String,
+    hash: u64
+}
+
+/// Collects the files referenced by the `Items` of a `Document` into an assets directory beside the LaTeX file.
+///
+/// When the `Document` is built, every referenced file is copied or hard-linked to the assets directory
+/// (`render_to_string` only rewrites the paths, see `Document::write_assets`),
+/// and the paths of the `Items` are rewritten to its copy, relative to the LaTeX file (`figures/latency.png`).
+/// A file with the same name beside the LaTeX file cannot be picked instead of the asset.
+/// Files with the same content are only put once in the assets directory.
+///
+/// Example:
+///
+/// ```rust
+/// # use rustex::*;
+/// # use std::fs::File;
+/// # use std::path::Path;
+/// # let doc_file: File = File::create("main.tex").unwrap();
+/// # let doc_class: DocumentClass = DocumentClass::new(ClassType::Article, vec![]);
+/// let mut doc: Document = Document::new(doc_file, doc_class);
+///
+/// let mut assets = AssetManager::new(Path::new("."));
+/// assets.set_directory("figures");
+/// assets.set_mode(AssetMode::HardLink);
+/// doc.set_assets(assets);
+///
+/// // `/tmp/run_42/latency.png` is written as `figures/latency.png`
+/// doc.add_item(Figure::new("h", true, "/tmp/run_42/latency.png", "", None, "fig_latency"));
+/// ```
+///
+/// Generated LaTeX:
+///
+/// ```tex
+/// \includegraphics[]{figures/latency.png}
+/// ```
+#[derive(Clone, Debug)]
+pub struct AssetManager {
+    output_dir: PathBuf,
+    directory: String,
+    mode: AssetMode,
+    assets: Vec<Asset>,
+    names: HashMap<String, String>
+}
+
+impl AssetManager {
+    /// Initializes a new `AssetManager` object.
+    /// `output_dir` is the directory of the generated LaTeX file.
+    pub fn new(output_dir: &Path) -> Self {
+        Self {
+            output_dir: output_dir.to_path_buf(),
+            directory: String::from(DEFAULT_ASSETS_DIRECTORY),
+            mode: AssetMode::Copy,
+            assets: Vec::new(),
+            names: HashMap::new()
+        }
+    }
+
+    /// Sets the assets directory, relative to the directory of the generated LaTeX file.
+    pub fn set_directory(&mut self, directory: &str) {
+        self.directory = String::from(directory.trim_end_matches('/'));
+    }
+
+    pub fn set_mode(&mut self, mode: AssetMode) {
+        self.mode = mode;
+    }
+
+    /// Registers a file referenced by an `Item`.
+    /// Relative paths are resolved from the current directory, and a path without extension
+    /// is looked up with every image extension, like `\includegraphics` does.
+    pub fn register(&mut self, path: &str) -> Result<(), RustexError> {
+        if path.is_empty() || self.names.contains_key(path) {
+            return Ok(());
+        }
+
+        let source = resolve_image(Path::new(path), &IMAGE_FORMATS)
+            .ok_or_else(|| RustexError::validation(&format!("asset '{}' does not exist", path)))?;
+        let hash = hash_file(&source)?;
+
+        let name = match self.find_duplicate(&source, hash)? {
+            Some(asset) => asset.name.clone(),
+            None => {
+                let name = self.get_free_name(&source);
+                self.assets.push(Asset { source, name: name.clone(), hash });
+                name
+            }
+        };
+
+        self.names.insert(String::from(path), name);
+        Ok(())
+    }
+
+    /// Gets the path of the copy of a registered file, relative to the directory of the generated LaTeX file.
+    /// Unregistered paths are returned as is.
+    pub fn get_path(&self, path: &str) -> String {
+        match self.names.get(path) {
+            Some(name) if self.directory.is_empty() => name.clone(),
+            Some(name) => format!("{}/{}", self.directory, name),
+            None => String::from(path)
+        }
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.assets.clear();
+        self.names.clear();
+    }
+
+    /// Copies or hard-links the registered files to the assets directory.
+    /// Files already up to date in the assets directory are left untouched.
+    pub(crate) fn write_assets(&self) -> Result<(), RustexError> {
+        let assets_dir = self.output_dir.join(&self.directory);
+        fs::create_dir_all(&assets_dir)?;
+
+        for asset in &self.assets {
+            let destination = assets_dir.join(&asset.name);
+
+            if destination.is_file() {
+                if hash_file(&destination)? == asset.hash && same_content(&destination, &asset.source)? {
+                    continue;
+                }
+
+                fs::remove_file(&destination)?;
+            }
+
+            match self.mode {
+                AssetMode::Copy => copy_file(&asset.source, &destination)?,
+                AssetMode::HardLink => {
+                    if fs::hard_link(&asset.source, &destination).is_err() {
+                        copy_file(&asset.source, &destination)?;
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn find_duplicate(&self, source: &Path, hash: u64) -> Result<Option<&Asset>, RustexError> {
+        for asset in self.assets.iter().filter(|asset| asset.hash == hash) {
+            if same_content(&asset.source, source)? {
+                return Ok(Some(asset));
+            }
+        }
+
+        Ok(None)
+    }
+
+    // Dots and spaces in file names are not supported by every LaTeX engine
+    fn get_free_name(&self, source: &Path) -> String {
+        let stem: String = source.file_stem()
+            .map(|stem| stem.to_string_lossy())
+            .unwrap_or_default()
+            .chars()
+            .map(|character| match character.is_ascii_alphanumeric() || character == '-' || character == '_' {
+                true => character,
+                false => '_'
+            })
+            .collect();
+
+        let extension = source.extension()
+            .map(|extension| format!(".{}", extension.to_string_lossy()))
+            .unwrap_or_default();
+
+        let mut name = format!("{}{}", stem, extension);
+        let mut index = 2;
+
+        while self.assets.iter().any(|asset| asset.name == name) {
+            name = format!("{}_{}{}", stem, index, extension);
+            index += 1;
+        }
+
+        name
+    }
+}
+
+fn copy_file(source: &Path, destination: &Path) -> Result<(), RustexError> {
+    fs::copy(source, destination)?;
+    Ok(())
+}
+
+fn hash_file(path: &Path) -> Result<u64, RustexError> {
+    let mut reader = BufReader::new(File::open(path)?);
+    let mut hasher = DefaultHasher::new();
+    let mut buffer = [0u8; 8192];
+
+    loop {
+        let read = reader.read(&mut buffer)?;
+
+        if read == 0 {
+            return Ok(hasher.finish());
+        }
+
+        hasher.write(&buffer[..read]);
+    }
+}
+
+// Equal hashes do not guarantee equal contents
+fn same_content(first: &Path, second: &Path) -> Result<bool, RustexError> {
+    if fs::metadata(first)?.len() != fs::metadata(second)?.len() {
+        return Ok(false);
+    }
+
+    Ok(fs::read(first)? == fs::read(second)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::env;
+    use super::*;
+    use crate::components::document::*;
+    use crate::components::item::*;
+
+    // A directory with the given files, removed when the test ends
+    struct TempDir {
+        path: PathBuf
+    }
+
+    impl TempDir {
+        fn new(name: &str, files: &[(&str, &str)]) -> Self {
+            let path = env::temp_dir().join(format!("rustex_assets_{}_{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&path);
+
+            for (file, content) in files {
+                let file_path = path.join(file);
+                fs::create_dir_all(file_path.parent().unwrap()).unwrap();
+                fs::write(file_path, content).unwrap();
+            }
+
+            Self { path }
+        }
+
+        fn file(&self, file: &str) -> String {
+            self.path.join(file).to_string_lossy().into_owned()
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.path);
+        }
+    }
+
+    #[test]
+    fn rewrites_paths_relative_to_the_latex_file() {
+        let dir = TempDir::new("paths", &[
+            ("run_1/latency.png", "first"),
+            ("run_2/latency.png", "second"),
+            ("run_3/latency copy.png", "first")
+        ]);
+
+        let mut assets = AssetManager::new(&dir.path.join("out"));
+        assets.set_directory("figures/");
+
+        for run in ["run_1/latency.png", "run_2/latency.png", "run_3/latency copy.png"] {
+            assets.register(&dir.file(run)).unwrap();
+        }
+
+        assert_eq!(assets.get_path(&dir.file("run_1/latency.png")), "figures/latency.png");
+        assert_eq!(assets.get_path(&dir.file("run_2/latency.png")), "figures/latency_2.png");
+        assert_eq!(assets.get_path(&dir.file("run_3/latency copy.png")), "figures/latency.png");
+        assert_eq!(assets.get_path("unregistered.png"), "unregistered.png");
+
+        assets.write_assets().unwrap();
+        assert_eq!(fs::read_to_string(dir.path.join("out/figures/latency.png")).unwrap(), "first");
+        assert_eq!(fs::read_to_string(dir.path.join("out/figures/latency_2.png")).unwrap(), "second");
+    }
+
+    #[test]
+    fn writes_asset_paths_without_graphicspath() {
+        let dir = TempDir::new("document", &[("plots/latency.png", "plot")]);
+
+        let mut doc = Document::from_writer(Vec::new(), DocumentClass::new(ClassType::Article, vec![]));
+        doc.set_assets(AssetManager::new(&dir.path.join("out")));
+        doc.add_item(Figure::new("h", true, &dir.file("plots/latency.png"), "", None, "fig_latency"));
+
+        let latex = doc.render_to_string().unwrap();
+
+        assert!(latex.contains(&format!(r"\includegraphics[]{{{}/latency.png}}", DEFAULT_ASSETS_DIRECTORY)), "{}", latex);
+        assert!(!latex.contains(r"\graphicspath"), "{}", latex);
+    }
+
+    #[test]
+    fn renders_to_string_without_writing_assets() {
+        let dir = TempDir::new("render", &[("plots/latency.png", "plot")]);
+
+        let mut doc = Document::from_writer(Vec::new(), DocumentClass::new(ClassType::Article, vec![]));
+        doc.set_assets(AssetManager::new(&dir.path.join("out")));
+        doc.add_item(Figure::new("h", true, &dir.file("plots/latency.png"), "", None, "fig_latency"));
+
+        doc.render_to_string().unwrap();
+        assert!(!dir.path.join("out").exists());
+
+        doc.write_assets().unwrap();
+        assert_eq!(fs::read_to_string(dir.path.join("out").join(DEFAULT_ASSETS_DIRECTORY).join("latency.png")).unwrap(), "plot");
+    }
+}
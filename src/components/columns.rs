@@ -0,0 +1,234 @@
+use std::fmt;
+use super::graphics::Length;
+use super::package::*;
+use super::table::CellAlignment;
+use crate::error::RustexError;
+use crate::utilities::def_syntax::*;
+use crate::utilities::format::*;
+
+/// Defines the vertical alignment of a paragraph column of a `Table`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParagraphAlignment {
+    /// `p{}`: aligned on the first line of the row.
+    Top,
+    /// `m{}`: centered vertically (`array` package).
+    Middle,
+    /// `b{}`: aligned on the last line of the row (`array` package).
+    Bottom
+}
+
+impl ParagraphAlignment {
+    pub fn get_def(&self) -> &str {
+        match self {
+            ParagraphAlignment::Top => "p",
+            ParagraphAlignment::Middle => "m",
+            ParagraphAlignment::Bottom => "b"
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum ColumnPart {
+    Column(String),
+    Paragraph(ParagraphAlignment, Length),
+    Numeric(String),
+    Rule,
+    Separator(String),
+    Before(String),
+    After(String),
+    Repeat(usize, ColumnSpec)
+}
+
+/// The column specification of a `Table`, e.g. `|l|c|p{3cm}|`.
+/// Refer to `tabular` in LaTeX documentation for more information.
+///
+/// Unlike raw options, a `ColumnSpec` always knows its number of columns,
+/// so every `TableRow` of the `Table` is checked against it.
+/// The `array` and `siunitx` packages are added to the `Document` automatically when needed.
+///
+/// Example:
+///
+/// ```rust
+/// # use rustex::*;
+/// let columns = ColumnSpec::new()
+///     .separator("")
+///     .before(r"\bfseries")
+///     .left()
+///     .rule()
+///     .paragraph(ParagraphAlignment::Middle, Length::Cm(4.0))
+///     .repeat(2, ColumnSpec::new().numeric("table-format=3.2"))
+///     .separator("");
+///
+/// assert_eq!(columns.to_string(), r"@{}>{\bfseries}l|m{4cm}*{2}{S[table-format=3.2]}@{}");
+/// assert_eq!(columns.get_column_count(), 4);
+///
+/// let table = Table::from_columns("H", columns, true, None, "tab_results");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct ColumnSpec {
+    parts: Vec<ColumnPart>
+}
+
+impl ColumnSpec {
+    /// Initializes a new `ColumnSpec` object, without any column
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn left(self) -> Self {
+        self.align(CellAlignment::Left)
+    }
+
+    pub fn center(self) -> Self {
+        self.align(CellAlignment::Center)
+    }
+
+    pub fn right(self) -> Self {
+        self.align(CellAlignment::Right)
+    }
+
+    /// Adds a column with the given alignment.
+    pub fn align(mut self, alignment: CellAlignment) -> Self {
+        self.parts.push(ColumnPart::Column(String::from(alignment.get_def())));
+        self
+    }
+
+    /// Adds a column of fixed width, whose content is wrapped in a paragraph.
+    pub fn paragraph(mut self, alignment: ParagraphAlignment, width: Length) -> Self {
+        self.parts.push(ColumnPart::Paragraph(alignment, width));
+        self
+    }
+
+    /// Adds a column of numbers aligned on their decimal marker (`siunitx` `S` column).
+    /// The options are passed to the column as is, e.g. `table-format=3.2`.
+    pub fn numeric(mut self, options: &str) -> Self {
+        self.parts.push(ColumnPart::Numeric(String::from(options)));
+        self
+    }
+
+    /// Adds a vertical rule between two columns.
+    pub fn rule(mut self) -> Self {
+        self.parts.push(ColumnPart::Rule);
+        self
+    }
+
+    /// Replaces the space between two columns by the given LaTeX code (`@{}`).
+    /// An empty separator removes the space.
+    pub fn separator(mut self, separator: &str) -> Self {
+        self.parts.push(ColumnPart::Separator(String::from(separator)));
+        self
+    }
+
+    /// Inserts LaTeX code before the content of every cell of the next column (`>{}`).
+    pub fn before(mut self, code: &str) -> Self {
+        self.parts.push(ColumnPart::Before(String::from(code)));
+        self
+    }
+
+    /// Inserts LaTeX code after the content of every cell of the previous column (`<{}`).
+    pub fn after(mut self, code: &str) -> Self {
+        self.parts.push(ColumnPart::After(String::from(code)));
+        self
+    }
+
+    /// Repeats a `ColumnSpec` several times (`*{}{}`).
+    pub fn repeat(mut self, count: usize, columns: ColumnSpec) -> Self {
+        self.parts.push(ColumnPart::Repeat(count, columns));
+        self
+    }
+
+    /// Gets the number of columns of the `ColumnSpec`.
+    pub fn get_column_count(&self) -> usize {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                ColumnPart::Column(_) | ColumnPart::Paragraph(_, _) | ColumnPart::Numeric(_) => 1,
+                ColumnPart::Repeat(count, columns) => count * columns.get_column_count(),
+                _ => 0
+            })
+            .sum()
+    }
+
+    pub(crate) fn required_packages(&self) -> Vec<Package> {
+        let mut packages = Vec::new();
+
+        for part in &self.parts {
+            match part {
+                ColumnPart::Paragraph(ParagraphAlignment::Top, _) => {}
+                ColumnPart::Paragraph(_, _) | ColumnPart::Before(_) | ColumnPart::After(_) => {
+                    packages.push(Package::new(ARRAY_PACKAGE, vec![]));
+                }
+                ColumnPart::Numeric(_) => packages.push(Package::new(SIUNITX_PACKAGE, vec![])),
+                ColumnPart::Repeat(_, columns) => packages.extend(columns.required_packages()),
+                _ => {}
+            }
+        }
+
+        packages
+    }
+
+    /// Checks that every `>{}` is followed by a column and that every `<{}` follows a column.
+    pub(crate) fn validate(&self) -> Result<(), RustexError> {
+        for (index, part) in self.parts.iter().enumerate() {
+            match part {
+                ColumnPart::Before(code) if !self.parts.get(index + 1).is_some_and(|next| next.starts_with_column()) => {
+                    return Err(RustexError::validation(&format!("'>{{{}}}' is not followed by a column", code)));
+                }
+                ColumnPart::After(code) if index == 0 || !self.parts[index - 1].ends_with_column() => {
+                    return Err(RustexError::validation(&format!("'<{{{}}}' does not follow a column", code)));
+                }
+                ColumnPart::Repeat(_, columns) => columns.validate()?,
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl ColumnPart {
+    fn is_column(&self) -> bool {
+        matches!(self, ColumnPart::Column(_) | ColumnPart::Paragraph(_, _) | ColumnPart::Numeric(_))
+    }
+
+    // Decorations can be stacked, e.g. `>{\bfseries}>{\centering}c`
+    fn starts_with_column(&self) -> bool {
+        match self {
+            ColumnPart::Before(_) => true,
+            ColumnPart::Repeat(_, columns) => columns.parts.first().is_some_and(|part| part.starts_with_column()),
+            _ => self.is_column()
+        }
+    }
+
+    fn ends_with_column(&self) -> bool {
+        match self {
+            ColumnPart::After(_) => true,
+            ColumnPart::Repeat(_, columns) => columns.parts.last().is_some_and(|part| part.ends_with_column()),
+            _ => self.is_column()
+        }
+    }
+}
+
+impl fmt::Display for ColumnSpec {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for part in &self.parts {
+            match part {
+                ColumnPart::Column(alignment) => write!(f, "{}", alignment)?,
+                ColumnPart::Paragraph(alignment, width) => {
+                    write!(f, "{}{}", alignment.get_def(), into_braces(&width.to_string()))?
+                }
+                ColumnPart::Numeric(options) if options.is_empty() => write!(f, "{}", SIUNITX_COLUMN)?,
+                ColumnPart::Numeric(options) => write!(f, "{}{}", SIUNITX_COLUMN, into_brackets(options))?,
+                ColumnPart::Rule => write!(f, "{}", DEF_VERTICAL_RULE)?,
+                ColumnPart::Separator(separator) => write!(f, "@{}", into_braces(separator))?,
+                ColumnPart::Before(code) => write!(f, ">{}", into_braces(code))?,
+                ColumnPart::After(code) => write!(f, "<{}", into_braces(code))?,
+                ColumnPart::Repeat(count, columns) => {
+                    write!(f, "*{}{}", into_braces(&count.to_string()), into_braces(&columns.to_string()))?
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -1,6 +1,10 @@
-use std::io::{Write, Error};
+use std::io::Write;
+use super::asset::*;
 use super::item::*;
 use super::document::*;
+use super::package::*;
+use super::reference::*;
+use crate::error::RustexError;
 use crate::utilities::def_syntax::*;
 use crate::utilities::format::*;
 
@@ -10,11 +14,163 @@ pub trait Container: Item {
     fn update_nested_indent(&mut self);
 }
 
+/// An entry of a list `Container` (`Enumerate`, `Itemize` or `Description`).
+/// A custom label replaces the default bullet or number (`\item[label]`).
+struct ListItem {
+    label: Option<Text>,
+    item: Box<dyn Item>
+}
+
+impl ListItem {
+    fn build(&self, doc: &Document, indent: &usize) -> Result<(), RustexError> {
+        match &self.label {
+            Some(label) => {
+                let item_str = format!("{}{}", DEF_ITEM, into_brackets(&into_braces(&label.render_inline()?)));
+                write_indented_line(doc, indent, &item_str)?;
+            }
+            None => write_indented_line(doc, indent, DEF_ITEM)?
+        }
+
+        doc.build_item(self.item.as_ref())
+    }
+}
+
+fn build_list_items(doc: &Document, items: &[ListItem], indent: &usize) -> Result<(), RustexError> {
+    for item in items {
+        item.build(doc, &(indent + 1))?;
+    }
+
+    Ok(())
+}
+
+fn update_list_indent(items: &mut [ListItem], indent: &usize) {
+    for item in items {
+        item.item.update_indent(indent);
+    }
+}
+
+fn list_required_packages(items: &[ListItem]) -> Vec<Package> {
+    items
+        .iter()
+        .flat_map(|item| {
+            let label_packages = item.label.iter().flat_map(|label| label.required_packages());
+            label_packages.chain(item.item.required_packages()).collect::<Vec<Package>>()
+        })
+        .collect()
+}
+
+fn register_list_labels(items: &[ListItem], registry: &mut LabelRegistry) -> Result<(), RustexError> {
+    for item in items {
+        if let Some(label) = &item.label {
+            label.register_labels(registry)?;
+        }
+
+        item.item.register_labels(registry).map_err(|error| error.within(item.item.describe()))?;
+    }
+
+    Ok(())
+}
+
+fn check_list_references(items: &[ListItem], registry: &LabelRegistry) -> Result<(), RustexError> {
+    for item in items {
+        if let Some(label) = &item.label {
+            label.check_references(registry)?;
+        }
+
+        item.item.check_references(registry).map_err(|error| error.within(item.item.describe()))?;
+    }
+
+    Ok(())
+}
+
+fn validate_list_items(items: &[ListItem]) -> Result<(), RustexError> {
+    for item in items {
+        if let Some(label) = &item.label {
+            label.validate_inline()?;
+        }
+
+        item.item.validate().map_err(|error| error.within(item.item.describe()))?;
+    }
+
+    Ok(())
+}
+
+fn register_list_assets(items: &[ListItem], assets: &mut AssetManager) -> Result<(), RustexError> {
+    for item in items {
+        item.item.register_assets(assets).map_err(|error| error.within(item.item.describe()))?;
+    }
+
+    Ok(())
+}
+
+// Starred sectioning commands do not write an entry in the table of contents by themselves.
+// With `hyperref`, an anchor is needed so that the entry links to the section instead of the previous one
+fn build_contents_line(doc: &Document, indent: &usize, sectioning_def: &str, name: &str) -> Result<(), RustexError> {
+    if doc.is_package_loaded(HYPERREF_PACKAGE) {
+        write_indented_line(doc, indent, DEF_PHANTOM_SECTION)?;
+    }
+
+    let level = String::from(sectioning_def.trim_start_matches('\\'));
+    let contents_line_str = format!("{}{}{}{}", DEF_ADD_CONTENTS_LINE, into_braces(&String::from(TOC_FILE)),
+                                    into_braces(&level), into_braces(&String::from(name)));
+
+    write_indented_line(doc, indent, &contents_line_str)
+}
+
+/// Defines the numbering style of an `Enumerate`.
+pub enum NumberingStyle {
+    Arabic,
+    LowerRoman,
+    UpperRoman,
+    LowerAlpha,
+    UpperAlpha
+}
+
+impl NumberingStyle {
+    pub fn get_def(&self) -> &str {
+        match self {
+            NumberingStyle::Arabic => DEF_ARABIC_COUNTER,
+            NumberingStyle::LowerRoman => DEF_LOWER_ROMAN_COUNTER,
+            NumberingStyle::UpperRoman => DEF_UPPER_ROMAN_COUNTER,
+            NumberingStyle::LowerAlpha => DEF_LOWER_ALPHA_COUNTER,
+            NumberingStyle::UpperAlpha => DEF_UPPER_ALPHA_COUNTER
+        }
+    }
+}
+
 /// An object that can list `Items`.
 /// Refer to `enumerate` in LaTeX documentation for more information.
+///
+/// The numbering style, the start value and the resuming of a previous list can be customized.
+/// The `enumitem` package is then added to the `Document` automatically.
+///
+/// Example:
+///
+/// ```rust
+/// # use rustex::*;
+/// let mut steps: Enumerate = Enumerate::new("steps_1");
+/// steps.set_numbering(NumberingStyle::Arabic, "Step {}:");
+/// steps.add_item(Text::new("Collect the data"));
+///
+/// let mut more_steps: Enumerate = Enumerate::new("steps_2");
+/// more_steps.set_numbering(NumberingStyle::Arabic, "Step {}:");
+/// more_steps.set_resume(true);
+/// more_steps.add_item(Text::new("Plot the data"));
+/// ```
+///
+/// Generated LaTeX:
+///
+/// ```tex
+/// \begin{enumerate}[label={Step \arabic*:}] \label{steps_1}
+/// ...
+/// \begin{enumerate}[label={Step \arabic*:}, resume] \label{steps_2}
+/// ```
 pub struct Enumerate {
-    items: Vec<Box<dyn Item>>,
+    items: Vec<ListItem>,
     label: String,
+    numbering: Option<String>,
+    start: Option<usize>,
+    resume: bool,
     indent: usize
 }
 
@@ -24,40 +180,91 @@ impl Enumerate {
         Self {
             items: Vec::new(),
             label: String::from(_label),
+            numbering: None,
+            start: None,
+            resume: false,
             indent: 0
         }
     }
 
+    /// Gets the `Label` of the `Enumerate`, to be referenced in a `Text`.
+    pub fn get_label(&self) -> Label {
+        Label::new(&self.label)
+    }
+
+    /// Sets the numbering style of the `Enumerate`.
+    /// `{}` in the format is replaced by the number, e.g. `"{}."`, `"({})"` or `"Step {}:"`.
+    pub fn set_numbering(&mut self, style: NumberingStyle, format: &str) {
+        let numbering = format
+            .split("{}")
+            .map(escape_text)
+            .collect::<Vec<String>>()
+            .join(style.get_def());
+
+        self.numbering = Some(numbering);
+    }
+
+    /// Sets the value of the first item of the `Enumerate`.
+    pub fn set_start(&mut self, start: usize) {
+        self.start = Some(start);
+    }
+
+    /// Continues the numbering of the previous `Enumerate` instead of restarting it.
+    pub fn set_resume(&mut self, resume: bool) {
+        self.resume = resume;
+    }
+
+    fn get_options(&self) -> Vec<String> {
+        let mut options: Vec<String> = Vec::new();
+
+        if let Some(numbering) = &self.numbering {
+            options.push(format!("{}={}", ENUMITEM_LABEL_OPTION, into_braces(numbering)));
+        }
+
+        if let Some(start) = self.start {
+            options.push(format!("{}={}", ENUMITEM_START_OPTION, start));
+        }
+
+        if self.resume {
+            options.push(String::from(ENUMITEM_RESUME_OPTION));
+        }
+
+        options
+    }
+
     /// Adds an `Item` to this `Container`
     pub fn add_item<I: Item + 'static>(&mut self, item: I) {
-        self.items.push(Box::new(item));
+        self.items.push(ListItem { label: None, item: Box::new(item) });
     }
 
-    fn build_header(&self, doc: &Document) -> Result<(), Error> {
-        let begin_enumerate_str = format!("{} {}", DEF_BEGIN_ENUMERATE, into_label(&self.label));
-        write_indented_line(&doc, &self.indent, &begin_enumerate_str)?;
-        doc.add_blank_line()
+    /// Adds an `Item` with a custom label (`\item[label]`) to this `Container`
+    pub fn add_labeled_item<I: Item + 'static>(&mut self, label: Text, item: I) {
+        self.items.push(ListItem { label: Some(label), item: Box::new(item) });
     }
 
-    fn build_items(&self, doc: &Document) -> Result<(), Error> {
-        for item in &self.items {
-            write_indented_line(&doc, &(self.indent + 1), DEF_ITEM_ENUMERATE)?;
-            item.build(doc)?;
+    fn build_header(&self, doc: &Document) -> Result<(), RustexError> {
+        let options = self.get_options();
+        let mut begin_enumerate_str = String::from(DEF_BEGIN_ENUMERATE);
+
+        if !options.is_empty() {
+            begin_enumerate_str = format!("{}{}", begin_enumerate_str, into_brackets(&options.join(", ")));
         }
 
-        Ok(())
+        begin_enumerate_str = format!("{} {}", begin_enumerate_str, into_label(&self.label));
+        write_indented_line(doc, &self.indent, &begin_enumerate_str)?;
+        doc.add_blank_line()
     }
 
-    fn build_end(&self, doc: &Document) -> Result<(), Error> {
-        write_indented_line(&doc, &self.indent, DEF_END_ENUMERATE)?;
+    fn build_end(&self, doc: &Document) -> Result<(), RustexError> {
+        write_indented_line(doc, &self.indent, DEF_END_ENUMERATE)?;
         doc.add_blank_line()
     }
 }
 
 impl Item for Enumerate {
-    fn build(&self, doc: &Document) -> Result<(), Error> {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
         self.build_header(doc)?;
-        self.build_items(doc)?;
+        build_list_items(doc, &self.items, &self.indent)?;
         self.build_end(doc)
     }
 
@@ -65,14 +272,232 @@ impl Item for Enumerate {
         self.indent = super_indent + 1;
         self.update_nested_indent();
     }
+
+    fn describe(&self) -> String {
+        format!("Enumerate '{}'", self.label)
+    }
+
+    fn required_packages(&self) -> Vec<Package> {
+        let mut packages = list_required_packages(&self.items);
+
+        if !self.get_options().is_empty() {
+            packages.push(Package::new(ENUMITEM_PACKAGE, vec![]));
+        }
+
+        packages
+    }
+
+    fn register_labels(&self, registry: &mut LabelRegistry) -> Result<(), RustexError> {
+        registry.register(&self.get_label())?;
+        register_list_labels(&self.items, registry)
+    }
+
+    fn check_references(&self, registry: &LabelRegistry) -> Result<(), RustexError> {
+        check_list_references(&self.items, registry)
+    }
+
+    fn validate(&self) -> Result<(), RustexError> {
+        validate_list_items(&self.items)
+    }
+
+    fn register_assets(&self, assets: &mut AssetManager) -> Result<(), RustexError> {
+        register_list_assets(&self.items, assets)
+    }
 }
 
 impl Container for Enumerate {
     fn update_nested_indent(&mut self) {
-        for item in &mut self.items {
-            item.update_indent(&self.indent);
+        update_list_indent(&mut self.items, &self.indent);
+    }
+}
+
+/// An object that lists `Items` with bullets.
+/// Refer to `itemize` in LaTeX documentation for more information.
+///
+/// Example:
+///
+/// ```rust
+/// # use rustex::*;
+/// let mut itemize: Itemize = Itemize::new();
+/// itemize.add_item(Text::new("First"));
+/// itemize.add_labeled_item(Text::raw(r"$\star$"), Text::new("Second"));
+/// ```
+///
+/// Generated LaTeX:
+///
+/// ```tex
+/// \begin{itemize}
+///
+///     \item
+///     First
+///
+///     \item[{$\star$}]
+///     Second
+///
+/// \end{itemize}
+/// ```
+pub struct Itemize {
+    items: Vec<ListItem>,
+    indent: usize
+}
+
+impl Itemize {
+    /// Initializes a new `Itemize` object
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            indent: 0
+        }
+    }
+
+    /// Adds an `Item` to this `Container`
+    pub fn add_item<I: Item + 'static>(&mut self, item: I) {
+        self.items.push(ListItem { label: None, item: Box::new(item) });
+    }
+
+    /// Adds an `Item` with a custom label (`\item[label]`) to this `Container`
+    pub fn add_labeled_item<I: Item + 'static>(&mut self, label: Text, item: I) {
+        self.items.push(ListItem { label: Some(label), item: Box::new(item) });
+    }
+}
+
+impl Default for Itemize {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Item for Itemize {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        write_indented_line(doc, &self.indent, DEF_BEGIN_ITEMIZE)?;
+        doc.add_blank_line()?;
+        build_list_items(doc, &self.items, &self.indent)?;
+        write_indented_line(doc, &self.indent, DEF_END_ITEMIZE)?;
+        doc.add_blank_line()
+    }
+
+    fn update_indent(&mut self, super_indent: &usize) {
+        self.indent = super_indent + 1;
+        self.update_nested_indent();
+    }
+
+    fn describe(&self) -> String {
+        String::from("Itemize")
+    }
+
+    fn required_packages(&self) -> Vec<Package> {
+        list_required_packages(&self.items)
+    }
+
+    fn register_labels(&self, registry: &mut LabelRegistry) -> Result<(), RustexError> {
+        register_list_labels(&self.items, registry)
+    }
+
+    fn check_references(&self, registry: &LabelRegistry) -> Result<(), RustexError> {
+        check_list_references(&self.items, registry)
+    }
+
+    fn validate(&self) -> Result<(), RustexError> {
+        validate_list_items(&self.items)
+    }
+
+    fn register_assets(&self, assets: &mut AssetManager) -> Result<(), RustexError> {
+        register_list_assets(&self.items, assets)
+    }
+}
+
+impl Container for Itemize {
+    fn update_nested_indent(&mut self) {
+        update_list_indent(&mut self.items, &self.indent);
+    }
+}
+
+/// An object that lists `Items` as terms and definitions.
+/// The label of each entry is its term (`\item[term]`).
+/// Refer to `description` in LaTeX documentation for more information.
+///
+/// Example:
+///
+/// ```rust
+/// # use rustex::*;
+/// let mut description: Description = Description::new();
+/// description.add_labeled_item(Text::new("Latency"), Text::new("Time to first byte"));
+/// description.add_labeled_item(Text::new("Throughput"), Text::new("Requests per second"));
+/// ```
+pub struct Description {
+    items: Vec<ListItem>,
+    indent: usize
+}
+
+impl Description {
+    /// Initializes a new `Description` object
+    pub fn new() -> Self {
+        Self {
+            items: Vec::new(),
+            indent: 0
         }
     }
+
+    /// Adds an `Item` without a term to this `Container`
+    pub fn add_item<I: Item + 'static>(&mut self, item: I) {
+        self.items.push(ListItem { label: None, item: Box::new(item) });
+    }
+
+    /// Adds an `Item` with its term (`\item[term]`) to this `Container`
+    pub fn add_labeled_item<I: Item + 'static>(&mut self, label: Text, item: I) {
+        self.items.push(ListItem { label: Some(label), item: Box::new(item) });
+    }
+}
+
+impl Default for Description {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Item for Description {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        write_indented_line(doc, &self.indent, DEF_BEGIN_DESCRIPTION)?;
+        doc.add_blank_line()?;
+        build_list_items(doc, &self.items, &self.indent)?;
+        write_indented_line(doc, &self.indent, DEF_END_DESCRIPTION)?;
+        doc.add_blank_line()
+    }
+
+    fn update_indent(&mut self, super_indent: &usize) {
+        self.indent = super_indent + 1;
+        self.update_nested_indent();
+    }
+
+    fn describe(&self) -> String {
+        String::from("Description")
+    }
+
+    fn required_packages(&self) -> Vec<Package> {
+        list_required_packages(&self.items)
+    }
+
+    fn register_labels(&self, registry: &mut LabelRegistry) -> Result<(), RustexError> {
+        register_list_labels(&self.items, registry)
+    }
+
+    fn check_references(&self, registry: &LabelRegistry) -> Result<(), RustexError> {
+        check_list_references(&self.items, registry)
+    }
+
+    fn validate(&self) -> Result<(), RustexError> {
+        validate_list_items(&self.items)
+    }
+
+    fn register_assets(&self, assets: &mut AssetManager) -> Result<(), RustexError> {
+        register_list_assets(&self.items, assets)
+    }
+}
+
+impl Container for Description {
+    fn update_nested_indent(&mut self) {
+        update_list_indent(&mut self.items, &self.indent);
+    }
 }
 
 /// A transparent object that contains `Items`
@@ -99,10 +524,16 @@ impl Block {
     }
 }
 
+impl Default for Block {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Item for Block {
-    fn build(&self, doc: &Document) -> Result<(), Error> {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
         for item in &self.items {
-            item.build(doc)?;
+            doc.build_item(item.as_ref())?;
         }
 
         Ok(())
@@ -112,6 +543,49 @@ impl Item for Block {
         self.indent = *super_indent;
         self.update_nested_indent();
     }
+
+    fn describe(&self) -> String {
+        String::from("Block")
+    }
+
+    fn required_packages(&self) -> Vec<Package> {
+        self.items
+            .iter()
+            .flat_map(|item| item.required_packages())
+            .collect()
+    }
+
+    fn register_labels(&self, registry: &mut LabelRegistry) -> Result<(), RustexError> {
+        for item in &self.items {
+            item.register_labels(registry).map_err(|error| error.within(item.describe()))?;
+        }
+
+        Ok(())
+    }
+
+    fn check_references(&self, registry: &LabelRegistry) -> Result<(), RustexError> {
+        for item in &self.items {
+            item.check_references(registry).map_err(|error| error.within(item.describe()))?;
+        }
+
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), RustexError> {
+        for item in &self.items {
+            item.validate().map_err(|error| error.within(item.describe()))?;
+        }
+
+        Ok(())
+    }
+
+    fn register_assets(&self, assets: &mut AssetManager) -> Result<(), RustexError> {
+        for item in &self.items {
+            item.register_assets(assets).map_err(|error| error.within(item.describe()))?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Container for Block {
@@ -146,6 +620,7 @@ pub struct Section {
     name: String,
     sec_type: SectionType,
     display_num: bool,
+    in_toc: bool,
     items: Vec<Box<dyn Item>>,
     label: String,
     indent: usize
@@ -158,6 +633,7 @@ impl Section {
             name: String::from(_name),
             sec_type: _sec_type,
             display_num: _display_num,
+            in_toc: false,
             items: Vec::new(),
             label: String::from(_label),
             indent: 0
@@ -169,21 +645,37 @@ impl Section {
         self.items.push(Box::new(item));
     }
 
-    fn build_header(&self, doc: &Document) -> Result<(), Error> {
-        indent_line(&doc, &self.indent)?;
+    /// Adds the `Section` to the table of contents even if it is unnumbered.
+    /// Numbered `Sections` are always in the table of contents.
+    pub fn set_in_toc(&mut self, in_toc: bool) {
+        self.in_toc = in_toc;
+    }
+
+    /// Gets the `Label` of the `Section`, to be referenced in a `Text`.
+    pub fn get_label(&self) -> Label {
+        Label::new(&self.label)
+    }
+
+    fn build_header(&self, doc: &Document) -> Result<(), RustexError> {
+        indent_line(doc, &self.indent)?;
 
         match self.display_num {
-            true => write!(doc.get_file(), "{}{}", self.sec_type.get_def(), into_braces(&self.name))?,
-            false => write!(doc.get_file(), "{}*{}", self.sec_type.get_def(), into_braces(&self.name))?
+            true => write!(doc.get_writer(), "{}{}", self.sec_type.get_def(), into_braces(&self.name))?,
+            false => write!(doc.get_writer(), "{}*{}", self.sec_type.get_def(), into_braces(&self.name))?
+        }
+
+        writeln!(doc.get_writer(), " {}", into_label(&self.label))?;
+
+        if !self.display_num && self.in_toc {
+            build_contents_line(doc, &self.indent, self.sec_type.get_def(), &self.name)?;
         }
 
-        writeln!(doc.get_file(), " {}", into_label(&self.label))?;
         doc.add_blank_line()
     }
 
-    fn build_items(&self, doc: &Document) -> Result<(), Error> {
+    fn build_items(&self, doc: &Document) -> Result<(), RustexError> {
         for item in &self.items {
-            item.build(doc)?;
+            doc.build_item(item.as_ref())?;
         }
 
         Ok(())
@@ -192,7 +684,7 @@ impl Section {
 }
 
 impl Item for Section {
-    fn build(&self, doc: &Document) -> Result<(), Error> {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
         self.build_header(doc)?;
         self.build_items(doc)
     }
@@ -201,6 +693,51 @@ impl Item for Section {
         self.indent = super_indent + 1;
         self.update_nested_indent();
     }
+
+    fn describe(&self) -> String {
+        format!("Section '{}'", self.name)
+    }
+
+    fn required_packages(&self) -> Vec<Package> {
+        self.items
+            .iter()
+            .flat_map(|item| item.required_packages())
+            .collect()
+    }
+
+    fn register_labels(&self, registry: &mut LabelRegistry) -> Result<(), RustexError> {
+        registry.register(&self.get_label())?;
+
+        for item in &self.items {
+            item.register_labels(registry).map_err(|error| error.within(item.describe()))?;
+        }
+
+        Ok(())
+    }
+
+    fn check_references(&self, registry: &LabelRegistry) -> Result<(), RustexError> {
+        for item in &self.items {
+            item.check_references(registry).map_err(|error| error.within(item.describe()))?;
+        }
+
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), RustexError> {
+        for item in &self.items {
+            item.validate().map_err(|error| error.within(item.describe()))?;
+        }
+
+        Ok(())
+    }
+
+    fn register_assets(&self, assets: &mut AssetManager) -> Result<(), RustexError> {
+        for item in &self.items {
+            item.register_assets(assets).map_err(|error| error.within(item.describe()))?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Container for Section {
@@ -216,6 +753,7 @@ impl Container for Section {
 pub struct Chapter {
     name: String,
     display_num: bool,
+    in_toc: bool,
     items: Vec<Box<dyn Item>>,
     label: String,
     indent: usize
@@ -227,6 +765,7 @@ impl Chapter {
         Self {
             name: String::from(_name),
             display_num: _display_num,
+            in_toc: false,
             items: Vec::new(),
             label: String::from(_label),
             indent: 0
@@ -238,21 +777,37 @@ impl Chapter {
         self.items.push(Box::new(item));
     }
 
-    fn build_header(&self, doc: &Document) -> Result<(), Error> {
-        indent_line(&doc, &self.indent)?;
+    /// Adds the `Chapter` to the table of contents even if it is unnumbered.
+    /// Numbered `Chapters` are always in the table of contents.
+    pub fn set_in_toc(&mut self, in_toc: bool) {
+        self.in_toc = in_toc;
+    }
+
+    /// Gets the `Label` of the `Chapter`, to be referenced in a `Text`.
+    pub fn get_label(&self) -> Label {
+        Label::new(&self.label)
+    }
+
+    fn build_header(&self, doc: &Document) -> Result<(), RustexError> {
+        indent_line(doc, &self.indent)?;
 
         match self.display_num {
-            true => write!(doc.get_file(), "{}{}", DEF_CHAPTER, into_braces(&self.name))?,
-            false => write!(doc.get_file(), "{}*{}", DEF_CHAPTER, into_braces(&self.name))?
+            true => write!(doc.get_writer(), "{}{}", DEF_CHAPTER, into_braces(&self.name))?,
+            false => write!(doc.get_writer(), "{}*{}", DEF_CHAPTER, into_braces(&self.name))?
+        }
+
+        writeln!(doc.get_writer(), " {}", into_label(&self.label))?;
+
+        if !self.display_num && self.in_toc {
+            build_contents_line(doc, &self.indent, DEF_CHAPTER, &self.name)?;
         }
 
-        writeln!(doc.get_file(), " {}", into_label(&self.label))?;
         doc.add_blank_line()
     }
 
-    fn build_items(&self, doc: &Document) -> Result<(), Error> {
+    fn build_items(&self, doc: &Document) -> Result<(), RustexError> {
         for item in &self.items {
-            item.build(doc)?;
+            doc.build_item(item.as_ref())?;
         }
 
         Ok(())
@@ -260,7 +815,7 @@ impl Chapter {
 }
 
 impl Item for Chapter {
-    fn build(&self, doc: &Document) -> Result<(), Error> {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
         self.build_header(doc)?;
         self.build_items(doc)
     }
@@ -269,6 +824,51 @@ impl Item for Chapter {
         self.indent = super_indent + 1;
         self.update_nested_indent();
     }
+
+    fn describe(&self) -> String {
+        format!("Chapter '{}'", self.name)
+    }
+
+    fn required_packages(&self) -> Vec<Package> {
+        self.items
+            .iter()
+            .flat_map(|item| item.required_packages())
+            .collect()
+    }
+
+    fn register_labels(&self, registry: &mut LabelRegistry) -> Result<(), RustexError> {
+        registry.register(&self.get_label())?;
+
+        for item in &self.items {
+            item.register_labels(registry).map_err(|error| error.within(item.describe()))?;
+        }
+
+        Ok(())
+    }
+
+    fn check_references(&self, registry: &LabelRegistry) -> Result<(), RustexError> {
+        for item in &self.items {
+            item.check_references(registry).map_err(|error| error.within(item.describe()))?;
+        }
+
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), RustexError> {
+        for item in &self.items {
+            item.validate().map_err(|error| error.within(item.describe()))?;
+        }
+
+        Ok(())
+    }
+
+    fn register_assets(&self, assets: &mut AssetManager) -> Result<(), RustexError> {
+        for item in &self.items {
+            item.register_assets(assets).map_err(|error| error.within(item.describe()))?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Container for Chapter {
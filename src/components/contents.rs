@@ -0,0 +1,238 @@
+use super::item::*;
+use super::document::*;
+use crate::error::RustexError;
+use crate::utilities::def_syntax::*;
+use crate::utilities::format::*;
+
+// The title is redefined in the document body, after `babel` has set the names of the language
+fn build_contents_list(doc: &Document, indent: &usize, command: &str, name_command: &str,
+                       title: &str, page_break: bool) -> Result<(), RustexError> {
+    if !title.is_empty() {
+        let renew_str = format!("{}{}{}", DEF_RENEW_COMMAND, into_braces(&String::from(name_command)),
+                                into_braces(&escape_text(title)));
+        write_indented_line(doc, indent, &renew_str)?;
+    }
+
+    write_indented_line(doc, indent, command)?;
+
+    if page_break {
+        write_indented_line(doc, indent, DEF_NEW_PAGE)?;
+    }
+
+    doc.add_blank_line()
+}
+
+/// The table of contents of a `Document`.
+/// Refer to `tableofcontents` in LaTeX documentation for more information.
+///
+/// Unnumbered `Sections` and `Chapters` are only listed if they are set `in_toc`.
+///
+/// Example:
+///
+/// ```rust
+/// # use rustex::*;
+/// let mut toc = TableOfContents::new();
+/// toc.set_depth(2);
+/// toc.set_title("Contents of the report");
+/// toc.set_page_break(true);
+///
+/// let mut acknowledgements = Chapter::new("Acknowledgements", false, "chap_ack");
+/// acknowledgements.set_in_toc(true);
+/// ```
+///
+/// Generated LaTeX:
+///
+/// ```tex
+/// \setcounter{tocdepth}{2}
+/// \renewcommand{\contentsname}{Contents of the report}
+/// \tableofcontents
+/// \newpage
+///
+/// \chapter*{Acknowledgements} \label{chap_ack}
+/// \addcontentsline{toc}{chapter}{Acknowledgements}
+/// ```
+pub struct TableOfContents {
+    depth: Option<usize>,
+    title: String,
+    page_break: bool,
+    indent: usize
+}
+
+impl TableOfContents {
+    /// Initializes a new `TableOfContents` object, with the depth and title defined by the class
+    pub fn new() -> Self {
+        Self {
+            depth: None,
+            title: String::new(),
+            page_break: false,
+            indent: 0
+        }
+    }
+
+    /// Sets the deepest level listed in the `TableOfContents` (`tocdepth`),
+    /// from 0 (chapters) to 5 (subparagraphs).
+    pub fn set_depth(&mut self, depth: usize) {
+        self.depth = Some(depth);
+    }
+
+    pub fn set_title(&mut self, title: &str) {
+        self.title = String::from(title);
+    }
+
+    /// Adds a page break after the `TableOfContents`.
+    pub fn set_page_break(&mut self, page_break: bool) {
+        self.page_break = page_break;
+    }
+}
+
+impl Default for TableOfContents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Item for TableOfContents {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        self.validate()?;
+
+        if let Some(depth) = self.depth {
+            let depth_str = format!("{}{}{}", DEF_SET_COUNTER, into_braces(&String::from(TOC_DEPTH_COUNTER)),
+                                    into_braces(&depth.to_string()));
+            write_indented_line(doc, &self.indent, &depth_str)?;
+        }
+
+        build_contents_list(doc, &self.indent, DEF_TABLE_OF_CONTENTS, DEF_CONTENTS_NAME,
+                            &self.title, self.page_break)
+    }
+
+    fn update_indent(&mut self, super_indent: &usize) {
+        self.indent = super_indent + 1;
+    }
+
+    fn describe(&self) -> String {
+        String::from("TableOfContents")
+    }
+
+    fn validate(&self) -> Result<(), RustexError> {
+        match self.depth {
+            Some(depth) if depth > MAX_TOC_DEPTH => Err(RustexError::validation(&format!(
+                "depth {} is greater than the maximum depth {}", depth, MAX_TOC_DEPTH
+            ))),
+            _ => Ok(())
+        }
+    }
+}
+
+/// The list of the captioned `Figures` of a `Document`.
+/// Refer to `listoffigures` in LaTeX documentation for more information.
+pub struct ListOfFigures {
+    title: String,
+    page_break: bool,
+    indent: usize
+}
+
+impl ListOfFigures {
+    /// Initializes a new `ListOfFigures` object, with the title defined by the class
+    pub fn new() -> Self {
+        Self {
+            title: String::new(),
+            page_break: false,
+            indent: 0
+        }
+    }
+
+    pub fn set_title(&mut self, title: &str) {
+        self.title = String::from(title);
+    }
+
+    /// Adds a page break after the `ListOfFigures`.
+    pub fn set_page_break(&mut self, page_break: bool) {
+        self.page_break = page_break;
+    }
+}
+
+impl Default for ListOfFigures {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Item for ListOfFigures {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        build_contents_list(doc, &self.indent, DEF_LIST_OF_FIGURES, DEF_LIST_FIGURE_NAME,
+                            &self.title, self.page_break)
+    }
+
+    fn update_indent(&mut self, super_indent: &usize) {
+        self.indent = super_indent + 1;
+    }
+
+    fn describe(&self) -> String {
+        String::from("ListOfFigures")
+    }
+}
+
+/// The list of the captioned `Tables` of a `Document`.
+/// Refer to `listoftables` in LaTeX documentation for more information.
+pub struct ListOfTables {
+    title: String,
+    page_break: bool,
+    indent: usize
+}
+
+impl ListOfTables {
+    /// Initializes a new `ListOfTables` object, with the title defined by the class
+    pub fn new() -> Self {
+        Self {
+            title: String::new(),
+            page_break: false,
+            indent: 0
+        }
+    }
+
+    pub fn set_title(&mut self, title: &str) {
+        self.title = String::from(title);
+    }
+
+    /// Adds a page break after the `ListOfTables`.
+    pub fn set_page_break(&mut self, page_break: bool) {
+        self.page_break = page_break;
+    }
+}
+
+impl Default for ListOfTables {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Item for ListOfTables {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        build_contents_list(doc, &self.indent, DEF_LIST_OF_TABLES, DEF_LIST_TABLE_NAME,
+                            &self.title, self.page_break)
+    }
+
+    fn update_indent(&mut self, super_indent: &usize) {
+        self.indent = super_indent + 1;
+    }
+
+    fn describe(&self) -> String {
+        String::from("ListOfTables")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn validates_the_depth() {
+        let mut contents = TableOfContents::new();
+        contents.set_depth(MAX_TOC_DEPTH);
+        assert!(contents.validate().is_ok());
+
+        contents.set_depth(MAX_TOC_DEPTH + 1);
+        assert_eq!(contents.validate().unwrap_err().to_string(),
+                   "validation error: depth 6 is greater than the maximum depth 5");
+    }
+}
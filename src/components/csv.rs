@@ -0,0 +1,474 @@
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use super::item::*;
+use super::table::*;
+use crate::error::RustexError;
+use crate::utilities::format::*;
+
+/// Defines whether the first record of a CSV file is a header.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CsvHeader {
+    /// The first record is a header if one of its columns is text above numbers.
+    /// Files with only text columns are read without a header, so that no record is lost:
+    /// use `CsvHeader::Present` for them.
+    Auto,
+    Present,
+    Absent
+}
+
+/// Defines how the numbers of a column are written in a `Table`.
+/// Fields that are not numbers are written as is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NumberFormat {
+    /// A fixed number of decimals, e.g. `3.14` for 2 decimals.
+    Decimals(usize),
+    /// A fixed number of decimals with a thousands separator, e.g. `1,234.50`.
+    Thousands(usize),
+    /// A percentage of a ratio with a fixed number of decimals, e.g. `12.5\%` for `0.125` and 1 decimal.
+    Percent(usize),
+    /// A scientific notation with a fixed number of decimals, e.g. `$1.50 \times 10^{3}$`.
+    Scientific(usize)
+}
+
+impl NumberFormat {
+    fn apply(&self, number: f64) -> String {
+        match self {
+            NumberFormat::Decimals(decimals) => format!("{:.*}", decimals, number),
+            NumberFormat::Thousands(decimals) => group_thousands(&format!("{:.*}", decimals, number)),
+            NumberFormat::Percent(decimals) => format!("{:.*}\\%", decimals, number * 100.0),
+            NumberFormat::Scientific(decimals) => {
+                let scientific = format!("{:.*e}", decimals, number);
+                let (mantissa, exponent) = scientific.split_once('e').unwrap_or((&scientific, "0"));
+                format!("${} \\times 10^{{{}}}$", mantissa, exponent)
+            }
+        }
+    }
+}
+
+fn group_thousands(number: &str) -> String {
+    let (sign, unsigned) = match number.strip_prefix('-') {
+        Some(unsigned) => ("-", unsigned),
+        None => ("", number)
+    };
+    let (integer, decimals) = match unsigned.split_once('.') {
+        Some((integer, decimals)) => (integer, format!(".{}", decimals)),
+        None => (unsigned, String::new())
+    };
+
+    let digits: Vec<char> = integer.chars().collect();
+    let groups: Vec<String> = digits
+        .rchunks(3)
+        .rev()
+        .map(|group| group.iter().collect())
+        .collect();
+
+    format!("{}{}{}", sign, groups.join(","), decimals)
+}
+
+/// Writes a field as an escaped `Text`, or as a formatted number.
+pub(crate) fn format_field(field: &str, format: Option<NumberFormat>) -> Text {
+    let field = field.trim();
+
+    match (format, parse_number(field)) {
+        (Some(format), Some(number)) => Text::raw(&format.apply(number)),
+        _ => Text::raw(&escape_text(field))
+    }
+}
+
+fn parse_number(field: &str) -> Option<f64> {
+    let field = field.trim();
+    let is_number_like = !field.is_empty()
+        && field.chars().all(|character| character.is_ascii_digit() || "+-.eE".contains(character))
+        && field.chars().any(|character| character.is_ascii_digit());
+
+    match is_number_like {
+        true => field.parse::<f64>().ok(),
+        false => None
+    }
+}
+
+/// The options used to read a CSV file into a `Table`.
+///
+/// Columns are selected, renamed and formatted by their header.
+/// Without a header, columns are named by their position, starting at `1`.
+///
+/// Example:
+///
+/// ```rust
+/// # use rustex::*;
+/// let csv = "\
+/// name;price;share
+/// Widget;1234.5;0.125
+/// \"Gadget; large\";99;0.5
+/// ";
+///
+/// let options = CsvOptions::new()
+///     .delimiter(';')
+///     .select(&["name", "price", "share"])
+///     .rename("name", "Product")
+///     .number_format("price", NumberFormat::Thousands(2))
+///     .number_format("share", NumberFormat::Percent(1));
+///
+/// let table = Table::from_csv_reader(csv.as_bytes(), &options, "H", true, None, "tab_products").unwrap();
+/// ```
+///
+/// Generated LaTeX:
+///
+/// ```tex
+/// \begin{table}[H]
+///     \centering
+///     \begin{tabular}{|l|r|r|}
+///         \hline
+///         \textbf{Product} & \textbf{price} & \textbf{share} \\
+///         \hline
+///         Widget & 1,234.50 & 12.5\% \\
+///         Gadget; large & 99.00 & 50.0\% \\
+///         \hline
+///     \end{tabular}
+///     \label{tab_products}
+/// \end{table}
+/// ```
+#[derive(Clone, Debug)]
+pub struct CsvOptions {
+    delimiter: char,
+    header: CsvHeader,
+    selected_columns: Option<Vec<String>>,
+    renamed_columns: Vec<(String, String)>,
+    number_formats: Vec<(String, NumberFormat)>
+}
+
+impl CsvOptions {
+    /// Initializes a new `CsvOptions` object, reading every column of a comma-separated file
+    pub fn new() -> Self {
+        Self {
+            delimiter: ',',
+            header: CsvHeader::Auto,
+            selected_columns: None,
+            renamed_columns: Vec::new(),
+            number_formats: Vec::new()
+        }
+    }
+
+    /// Sets the character separating the fields of a record, e.g. `;` or `\t`. Defaults to `,`.
+    pub fn delimiter(mut self, delimiter: char) -> Self {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Sets whether the first record of the file is a header. Defaults to `CsvHeader::Auto`.
+    pub fn header(mut self, header: CsvHeader) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Selects the columns written in the `Table`, in the given order.
+    pub fn select(mut self, columns: &[&str]) -> Self {
+        self.selected_columns = Some(columns.iter().map(|&column| String::from(column)).collect());
+        self
+    }
+
+    /// Renames a column in the header row of the `Table`.
+    pub fn rename(mut self, column: &str, name: &str) -> Self {
+        self.renamed_columns.push((String::from(column), String::from(name)));
+        self
+    }
+
+    /// Sets the `NumberFormat` of a column, named as in the CSV file, before any renaming.
+    pub fn number_format(mut self, column: &str, format: NumberFormat) -> Self {
+        self.number_formats.push((String::from(column), format));
+        self
+    }
+
+    fn has_header(&self, records: &[Vec<String>]) -> bool {
+        match self.header {
+            CsvHeader::Present => true,
+            CsvHeader::Absent => false,
+            CsvHeader::Auto => detect_header(records)
+        }
+    }
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn detect_header(records: &[Vec<String>]) -> bool {
+    let (first_record, other_records) = match records.split_first() {
+        Some((first_record, other_records)) if !other_records.is_empty() => (first_record, other_records),
+        _ => return false
+    };
+
+    (0..first_record.len()).any(|column| {
+        parse_number(&first_record[column]).is_none()
+            && !first_record[column].trim().is_empty()
+            && other_records.iter().all(|record| parse_number(&record[column]).is_some())
+    })
+}
+
+/// Parses CSV records, as described by RFC 4180.
+/// Fields can be quoted with `"`, and quotes are escaped by doubling them.
+fn parse_records(content: &str, delimiter: char) -> Result<Vec<Vec<String>>, RustexError> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut records: Vec<Vec<String>> = Vec::new();
+    let mut record: Vec<String> = Vec::new();
+    let mut field = String::new();
+    let mut is_quoted = false;
+    let mut line = 1;
+    let mut characters = content.chars().peekable();
+
+    while let Some(character) = characters.next() {
+        match character {
+            '"' if is_quoted && characters.peek() == Some(&'"') => {
+                field.push('"');
+                characters.next();
+            }
+            '"' if is_quoted => is_quoted = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                is_quoted = true;
+            }
+            '\n' if is_quoted => {
+                field.push(' ');
+                line += 1;
+            }
+            '\r' if is_quoted => {}
+            _ if is_quoted => field.push(character),
+            '\r' => {}
+            '\n' => {
+                record.push(std::mem::take(&mut field));
+                records.push(std::mem::take(&mut record));
+                line += 1;
+            }
+            _ if character == delimiter => record.push(std::mem::take(&mut field)),
+            _ => field.push(character)
+        }
+    }
+
+    if is_quoted {
+        return Err(RustexError::format(&format!("unterminated quoted field at line {}", line)));
+    }
+
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push(record);
+    }
+
+    records.retain(|record| record.iter().any(|field| !field.trim().is_empty()));
+
+    if let Some(first_record) = records.first() {
+        let field_count = first_record.len();
+
+        if let Some(index) = records.iter().position(|record| record.len() != field_count) {
+            return Err(RustexError::validation(&format!(
+                "record {} has {} field(s) but the first record has {}", index + 1, records[index].len(), field_count
+            )));
+        }
+    }
+
+    Ok(records)
+}
+
+fn find_column(names: &[String], column: &str) -> Result<usize, RustexError> {
+    names
+        .iter()
+        .position(|name| name == column)
+        .ok_or_else(|| RustexError::validation(&format!("column '{}' is not in the CSV file", column)))
+}
+
+impl Table {
+    /// Initializes a new `Table` object from a CSV file.
+    /// The header row is written in bold between horizontal lines, and every field is escaped.
+    /// Columns of numbers are aligned to the right.
+    /// Errors are reported within the path of the CSV file.
+    pub fn from_csv(_path: &Path, _options: &CsvOptions, _positioning: &str, _centered: bool,
+                    _caption: Option<Text>, _label: &str) -> Result<Self, RustexError> {
+        File::open(_path)
+            .map_err(RustexError::from)
+            .and_then(|file| Self::from_csv_reader(file, _options, _positioning, _centered, _caption, _label))
+            .map_err(|error| error.within(format!("CSV file '{}'", _path.display())))
+    }
+
+    /// Initializes a new `Table` object from CSV read from any `Read` source.
+    pub fn from_csv_reader<R: Read>(mut _reader: R, _options: &CsvOptions, _positioning: &str, _centered: bool,
+                                    _caption: Option<Text>, _label: &str) -> Result<Self, RustexError> {
+        let mut content = String::new();
+        _reader.read_to_string(&mut content)?;
+
+        let mut records = parse_records(&content, _options.delimiter)?;
+
+        if records.is_empty() {
+            return Err(RustexError::validation("the CSV file has no records"));
+        }
+
+        let field_count = records[0].len();
+        let has_header = _options.has_header(&records);
+
+        let names: Vec<String> = match has_header {
+            true => records.remove(0),
+            false => (1..=field_count).map(|position| position.to_string()).collect()
+        };
+
+        let selected_columns: Vec<usize> = match &_options.selected_columns {
+            Some(columns) => columns.iter().map(|column| find_column(&names, column)).collect::<Result<_, _>>()?,
+            None => (0..field_count).collect()
+        };
+
+        let mut formats: Vec<Option<NumberFormat>> = vec![None; field_count];
+        for (column, format) in &_options.number_formats {
+            formats[find_column(&names, column)?] = Some(*format);
+        }
+
+        let mut headers: Vec<String> = names.clone();
+        for (column, name) in &_options.renamed_columns {
+            headers[find_column(&names, column)?] = name.clone();
+        }
+
+        let alignments: Vec<CellAlignment> = selected_columns
+            .iter()
+            .map(|&column| {
+                match !records.is_empty() && records.iter().all(|record| parse_number(&record[column]).is_some()) {
+                    true => CellAlignment::Right,
+                    false => CellAlignment::Left
+                }
+            })
+            .collect();
+
+        let selected_headers: Vec<String> = selected_columns.iter().map(|&column| headers[column].clone()).collect();
+
+        let rows: Vec<Vec<Text>> = records
+            .iter()
+            .map(|record| {
+                selected_columns
+                    .iter()
+                    .map(|&column| format_field(&record[column], formats[column]))
+                    .collect()
+            })
+            .collect();
+
+        Ok(Table::from_rows(_positioning, &alignments, has_header.then_some(&selected_headers[..]), rows,
+                            _centered, _caption, _label))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::document::*;
+
+    fn render_table(table: Table) -> String {
+        let mut doc = Document::from_writer(Vec::new(), DocumentClass::new(ClassType::Article, vec![]));
+        doc.add_item(table);
+        doc.render_to_string().unwrap()
+    }
+
+    fn read_table(csv: &str, options: &CsvOptions) -> Result<Table, RustexError> {
+        Table::from_csv_reader(csv.as_bytes(), options, "H", false, None, "tab_csv")
+    }
+
+    fn records(rows: &[&[&str]]) -> Vec<Vec<String>> {
+        rows.iter().map(|row| row.iter().map(|field| String::from(*field)).collect()).collect()
+    }
+
+    #[test]
+    fn parses_quoted_fields() {
+        let csv = "name,quote\n\"Doe, John\",\"He said \"\"hi\"\"\"\n\"multi\nline\",\"\"\n";
+
+        assert_eq!(parse_records(csv, ',').unwrap(), records(&[
+            &["name", "quote"],
+            &["Doe, John", "He said \"hi\""],
+            &["multi line", ""]
+        ]));
+    }
+
+    #[test]
+    fn parses_other_delimiters() {
+        assert_eq!(parse_records("a;b\n\"1;5\";2", ';').unwrap(), records(&[&["a", "b"], &["1;5", "2"]]));
+        assert_eq!(parse_records("a\tb\n1\t2\n", '\t').unwrap(), records(&[&["a", "b"], &["1", "2"]]));
+    }
+
+    #[test]
+    fn parses_crlf_and_bom() {
+        let csv = "\u{feff}id,name\r\n1,\"A\r\nB\"\r\n2,C\r\n\r\n";
+
+        assert_eq!(parse_records(csv, ',').unwrap(), records(&[&["id", "name"], &["1", "A B"], &["2", "C"]]));
+    }
+
+    #[test]
+    fn rejects_unterminated_quotes() {
+        let error = parse_records("a,b\n1,\"open\n2,3\n", ',').unwrap_err();
+
+        assert_eq!(error.to_string(), "formatting error: unterminated quoted field at line 4");
+    }
+
+    #[test]
+    fn rejects_ragged_records() {
+        let error = parse_records("a,b,c\n1,2,3\n4,5\n", ',').unwrap_err();
+
+        assert_eq!(error.to_string(), "validation error: record 3 has 2 field(s) but the first record has 3");
+    }
+
+    #[test]
+    fn detects_headers() {
+        assert!(detect_header(&records(&[&["name", "price"], &["Widget", "1.5"], &["Gadget", "2"]])));
+        assert!(detect_header(&records(&[&["year", "2023"], &["1", "2"]])));
+        assert!(!detect_header(&records(&[&["Alice", "Smith"], &["Bob", "Jones"]])));
+        assert!(!detect_header(&records(&[&["1", "2"], &["3", "4"]])));
+        assert!(!detect_header(&records(&[&["", "2023"], &["Widget", "12"]])));
+        assert!(!detect_header(&records(&[&["name", "price"]])));
+        assert!(!detect_header(&[]));
+    }
+
+    #[test]
+    fn keeps_the_first_record_of_text_files() {
+        let latex = render_table(read_table("Alice,Smith\nBob,Jones\n", &CsvOptions::new()).unwrap());
+
+        assert!(latex.contains("Alice & Smith \\\\"));
+        assert!(!latex.contains(r"\textbf"));
+
+        let latex = render_table(read_table("Alice,Smith\nBob,Jones\n", &CsvOptions::new().header(CsvHeader::Present)).unwrap());
+
+        assert!(latex.contains(r"\textbf{Alice} & \textbf{Smith} \\"));
+    }
+
+    #[test]
+    fn writes_selected_and_formatted_columns() {
+        let options = CsvOptions::new()
+            .header(CsvHeader::Present)
+            .select(&["share", "name"])
+            .rename("name", "Product")
+            .number_format("share", NumberFormat::Percent(1));
+        let latex = render_table(read_table("name,share\nWidget_1,0.125\nGadget,n/a\n", &options).unwrap());
+
+        assert!(latex.contains(r"\begin{tabular}{|l|l|}"));
+        assert!(latex.contains(r"\textbf{share} & \textbf{Product} \\"));
+        assert!(latex.contains(r"12.5\% & Widget\_1 \\"));
+        assert!(latex.contains(r"n/a & Gadget \\"));
+    }
+
+    #[test]
+    fn rejects_empty_files() {
+        for csv in ["", "\n\n", "\u{feff}", " , \n"] {
+            let error = read_table(csv, &CsvOptions::new()).err().unwrap();
+            assert_eq!(error.to_string(), "validation error: the CSV file has no records", "CSV {:?}", csv);
+        }
+    }
+
+    #[test]
+    fn rejects_unknown_columns() {
+        let error = read_table("a,b\nx,1\n", &CsvOptions::new().select(&["c"])).err().unwrap();
+
+        assert_eq!(error.to_string(), "validation error: column 'c' is not in the CSV file");
+    }
+
+    #[test]
+    fn names_the_csv_file_in_errors() {
+        let path = std::env::temp_dir().join("rustex_missing_file.csv");
+        let error = Table::from_csv(&path, &CsvOptions::new(), "H", false, None, "tab_csv").err().unwrap();
+
+        assert!(matches!(error, RustexError::Io { .. }));
+        assert_eq!(error.path(), format!("CSV file '{}'", path.display()));
+    }
+}
@@ -1,6 +1,14 @@
+use std::cell::{RefCell, RefMut};
 use std::fs::File;
-use std::io::{Write, Error};
+use std::io::{Write, Error, ErrorKind};
+use std::rc::Rc;
+use super::asset::*;
 use super::item::*;
+use super::metadata::*;
+use super::package::*;
+use super::reference::*;
+use crate::diagnostic::SourceMap;
+use crate::error::RustexError;
 use crate::utilities::def_syntax::*;
 use crate::utilities::format::*;
 
@@ -9,24 +17,64 @@ use crate::utilities::format::*;
 /// `Containers` and `Items` can be added to the `Document` object.
 /// `Packages` and global `Commands` are located before the `\begin{document}` line.
 /// Other `Items` are located between the `\begin{document}` and `\end{document}` lines.
-pub struct Document {
-    file: File,
+///
+/// The title page metadata (title, authors, date, abstract, etc.) is written according to the `ClassType`
+/// of the `Document`, and is also set as the PDF metadata through `hyperref`.
+///
+/// The generated LaTeX is written to any `Write` sink (a `File`, a `Vec<u8>`, stdout, etc.).
+/// The sink may be borrowed, e.g. `&mut Vec<u8>` or a locked stdout, to read it back after `build`.
+/// While building, the `Document` records which `Item` wrote each line in a `SourceMap`.
+pub struct Document<'a> {
+    writer: RefCell<DocumentWriter<'a>>,
+    item_path: RefCell<Vec<String>>,
+    source_map: RefCell<SourceMap>,
     class: DocumentClass,
     packages: Vec<Package>,
     commands: Vec<Command>,
-    items: Vec<Box<dyn Item>>
+    items: Vec<Box<dyn Item>>,
+    assets: Option<AssetManager>,
+    metadata: Metadata,
+    loaded_packages: Vec<String>,
+    warnings: Vec<String>
 }
 
-impl Document {
+impl<'a> Document<'a> {
     /// Initializes a new `Document` object.
     /// A `File` and a `DocumentClass` need to be passed for the creation of the `Document`.
     pub fn new(doc_file: File, doc_class: DocumentClass) -> Self {
+        Self::from_writer(doc_file, doc_class)
+    }
+
+    /// Initializes a new `Document` object that writes to any `Write` sink.
+    /// Use `render_to_string` instead if the LaTeX is only needed as a `String`.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use rustex::*;
+    /// let mut latex = Vec::new();
+    ///
+    /// let doc_class = DocumentClass::new(ClassType::Article, vec![]);
+    /// let mut doc = Document::from_writer(&mut latex, doc_class);
+    /// doc.add_item(Text::new("Hello"));
+    /// doc.build().unwrap();
+    /// drop(doc);
+    ///
+    /// assert!(String::from_utf8(latex).unwrap().contains("Hello"));
+    /// ```
+    pub fn from_writer<W: Write + 'a>(writer: W, doc_class: DocumentClass) -> Self {
         Self {
-            file: doc_file,
+            writer: RefCell::new(DocumentWriter::new(Box::new(writer))),
+            item_path: RefCell::new(Vec::new()),
+            source_map: RefCell::new(SourceMap::default()),
             class: doc_class,
             packages: Vec::new(),
             commands: Vec::new(),
-            items: Vec::new()
+            items: Vec::new(),
+            assets: None,
+            metadata: Metadata::default(),
+            loaded_packages: Vec::new(),
+            warnings: Vec::new()
         }
     }
 
@@ -36,6 +84,8 @@ impl Document {
     }
 
     /// Adds a list of `Packages` to the `Document`.
+    /// `Packages` required by the `Items` are added automatically.
+    /// An added `Package` takes precedence over a required `Package` with the same name.
     pub fn add_packages(&mut self, _packages: Vec<Package>) {
         self.packages.extend(_packages);
     }
@@ -46,54 +96,245 @@ impl Document {
         self.commands.extend(_commands);
     }
 
+    /// Sets the title of the `Document`, written on its title page.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use rustex::*;
+    /// let doc_class = DocumentClass::new(ClassType::Article, vec![]);
+    /// let mut doc = Document::from_writer(Vec::new(), doc_class);
+    ///
+    /// doc.set_title("Annual Report");
+    /// doc.set_subtitle("Fiscal year 2024");
+    /// doc.add_authors(vec![
+    ///     Author::new("Ada Lovelace", "Analytical Engines Ltd.", "ada@example.com"),
+    ///     Author::new("Charles Babbage", "", "")
+    /// ]);
+    /// doc.set_date(DocumentDate::Today);
+    /// doc.add_keywords(vec!["finance", "report"]);
+    /// doc.set_abstract(Text::new("A summary of the **year**."));
+    ///
+    /// let latex = doc.render_to_string().unwrap();
+    /// assert!(latex.contains(r"\title{Annual Report \\ \large Fiscal year 2024}"));
+    /// assert!(latex.contains(r"\maketitle"));
+    /// ```
+    ///
+    /// Generated LaTeX:
+    ///
+    /// ```tex
+    /// % Title page
+    /// \title{Annual Report \\ \large Fiscal year 2024}
+    /// \author{Ada Lovelace \\ Analytical Engines Ltd. \\ \texttt{ada@example.com} \and Charles Babbage}
+    /// \date{\today}
+    /// \hypersetup{pdfinfo={Title={Annual Report}, Subject={Fiscal year 2024}, Author={Ada Lovelace, Charles Babbage}, Keywords={finance, report}}}
+    ///
+    /// \begin{document}
+    ///
+    ///     \maketitle
+    ///
+    ///     \begin{abstract}
+    ///         A summary of the \textbf{year}.
+    ///
+    ///         \par\medskip\noindent\textbf{Keywords:} finance, report
+    ///     \end{abstract}
+    /// ```
+    pub fn set_title(&mut self, _title: &str) {
+        self.metadata.title = String::from(_title);
+    }
+
+    /// Sets the subtitle of the `Document`, written below its title.
+    pub fn set_subtitle(&mut self, _subtitle: &str) {
+        self.metadata.subtitle = String::from(_subtitle);
+    }
+
+    /// Adds a list of `Authors` to the `Document`.
+    pub fn add_authors(&mut self, _authors: Vec<Author>) {
+        self.metadata.authors.extend(_authors);
+    }
+
+    /// Sets the date of the `Document`. Without a date, LaTeX writes the date of the compilation.
+    pub fn set_date(&mut self, _date: DocumentDate) {
+        self.metadata.date = Some(_date);
+    }
+
+    /// Adds a list of keywords to the `Document`.
+    /// The keywords are written below the abstract, if any, and in the PDF metadata.
+    pub fn add_keywords(&mut self, _keywords: Vec<&str>) {
+        self.metadata.keywords.extend(_keywords.iter().map(|&keyword| String::from(keyword)));
+    }
+
+    /// Sets the abstract of the `Document`, written after its title page.
+    pub fn set_abstract(&mut self, _abstract: Text) {
+        self.metadata.abstract_text = Some(_abstract);
+    }
+
+    /// Sets the `AssetManager` that collects the files referenced by the `Items` beside the LaTeX file.
+    /// Without an `AssetManager`, the paths of the `Items` are written as is.
+    pub fn set_assets(&mut self, _assets: AssetManager) {
+        self.assets = Some(_assets);
+    }
+
     /// Builds and generates the LaTeX file.
-    pub fn build(&mut self) {
+    /// Returns a `RustexError` carrying the path of the `Item` that failed.
+    /// The build fails on duplicate labels, on references to undefined labels and on invalid `Items`,
+    /// e.g. `Tables` with rows of the wrong width, before anything is written.
+    /// The files of the `AssetManager`, if any, are then written to the assets directory.
+    pub fn build(&mut self) -> Result<(), RustexError> {
+        self.build_document()?;
+        self.get_writer().flush()?;
+        self.write_collected_assets()
+    }
+
+    /// Builds the LaTeX document and returns it as a `String`.
+    /// The `Document`'s own writer is left untouched, and nothing is written to disk:
+    /// call `write_assets` to put the files of the `AssetManager` beside the LaTeX file.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use rustex::*;
+    /// let doc_class = DocumentClass::new(ClassType::Article, vec!["12pt"]);
+    /// let mut doc = Document::from_writer(Vec::new(), doc_class);
+    /// doc.add_item(Text::new("Hello **world**"));
+    ///
+    /// let latex = doc.render_to_string().unwrap();
+    /// assert!(latex.contains(r"Hello \textbf{world}"));
+    /// ```
+    pub fn render_to_string(&mut self) -> Result<String, RustexError> {
+        let buffer = SharedBuffer::default();
+        let writer = self.writer.replace(DocumentWriter::new(Box::new(buffer.clone())));
+
+        let result = self.build_document();
+
+        self.writer.replace(writer);
+        result?;
+        Ok(buffer.into_string()?)
+    }
+
+    /// Copies or hard-links the files referenced by the `Items` to the assets directory of the `AssetManager`.
+    /// Does nothing without an `AssetManager`. `build` already writes the assets.
+    pub fn write_assets(&mut self) -> Result<(), RustexError> {
+        self.collect_assets()?;
+        self.write_collected_assets()
+    }
+
+    fn write_collected_assets(&self) -> Result<(), RustexError> {
+        match &self.assets {
+            Some(assets) => assets.write_assets(),
+            None => Ok(())
+        }
+    }
+
+    fn build_document(&mut self) -> Result<(), RustexError> {
+        self.get_writer().reset_line_count();
+        self.source_map.borrow_mut().clear();
         self.update_indents();
-        self.build_doc_class().unwrap();
-        self.build_packages().unwrap();
-        self.build_commands().unwrap();
-        self.build_items().unwrap();
+        self.validate_labels()?;
+        self.validate_items()?;
+        self.collect_assets()?;
+        self.build_doc_class()?;
+        self.build_packages()?;
+        self.build_metadata()?;
+        self.build_commands()?;
+        self.build_items()
+    }
+
+    fn validate_labels(&self) -> Result<(), RustexError> {
+        let mut registry = LabelRegistry::new();
+
+        for item in &self.items {
+            item.register_labels(&mut registry).map_err(|error| error.within(item.describe()))?;
+        }
+
+        for item in &self.items {
+            item.check_references(&registry).map_err(|error| error.within(item.describe()))?;
+        }
+
+        self.metadata.check_references(&registry).map_err(|error| error.within(String::from(TITLE_PAGE_DESCRIPTION)))
     }
 
-    fn build_doc_class(&mut self) -> Result<(), Error> {
+    fn validate_items(&self) -> Result<(), RustexError> {
+        for item in &self.items {
+            item.validate().map_err(|error| error.within(item.describe()))?;
+        }
+
+        self.metadata.validate(&self.class._type).map_err(|error| error.within(String::from(TITLE_PAGE_DESCRIPTION)))
+    }
+
+    fn collect_assets(&mut self) -> Result<(), RustexError> {
+        if let Some(assets) = &mut self.assets {
+            assets.clear();
+
+            for item in &self.items {
+                item.register_assets(assets).map_err(|error| error.within(item.describe()))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn build_doc_class(&mut self) -> Result<(), RustexError> {
         let options_str = self.class.options.join(", ");
         let mut doc_class_str = format!("{}{}", DEF_DOCUMENT_CLASS, into_brackets(&options_str));
         doc_class_str = format!("{}{}", doc_class_str, into_braces(&self.class._type.to_str()));
 
-        writeln!(self.file, "{}", doc_class_str)?;
+        writeln!(self.get_writer(), "{}", doc_class_str)?;
         self.add_blank_line()
     }
 
-    fn build_packages(&mut self) -> Result<(), Error> {
-        self.build_default_packages()?;
+    fn build_packages(&mut self) -> Result<(), RustexError> {
+        let required_packages: Vec<Package> = self.items
+            .iter()
+            .flat_map(|item| item.required_packages())
+            .chain(self.metadata.required_packages())
+            .collect();
+
+        let (resolved_packages, warnings) = resolve_packages(&self.packages, required_packages);
+        self.warnings = warnings;
+        self.loaded_packages = self.packages
+            .iter()
+            .chain(resolved_packages.iter())
+            .map(|package| String::from(package.get_name()))
+            .collect();
 
-        writeln!(&self.file, "{}", USER_PACKAGES_COMMENT)?;
+        // Packages such as `hyperref` must also be loaded after the packages added by the user
+        let (late_packages, early_packages): (Vec<Package>, Vec<Package>) = resolved_packages
+            .into_iter()
+            .partition(|package| package.is_late());
+
+        self.build_package_list(REQUIRED_PACKAGES_COMMENT, &early_packages)?;
+
+        writeln!(self.get_writer(), "{}", USER_PACKAGES_COMMENT)?;
         for package in &self.packages {
-            package.build(&self)?;
+            self.record_build(package.describe(), || package.build(self))?;
         }
 
-        self.add_blank_line()
+        self.add_blank_line()?;
+        self.build_package_list(LATE_PACKAGES_COMMENT, &late_packages)
     }
 
-    fn build_default_packages(&mut self) -> Result<(), Error> {
-        let default_packages = vec![
-            DEFAULT_PACKAGES_COMMENT,
-            DEFAULT_FLOAT_PACKAGE,
-            DEFAULT_GRAPHICX_PACKAGE,
-            DEFAULT_XCOLOR_PACKAGE
-        ];
+    fn build_package_list(&self, comment: &str, packages: &[Package]) -> Result<(), RustexError> {
+        if packages.is_empty() {
+            return Ok(());
+        }
 
-        for package in default_packages {
-            writeln!(&self.file, "{}", package)?;
+        writeln!(self.get_writer(), "{}", comment)?;
+        for package in packages {
+            self.record_build(package.describe(), || package.build(self))?;
         }
 
         self.add_blank_line()
     }
 
-    fn build_commands(&mut self) -> Result<(), Error> {
-        writeln!(&self.file, "{}", USER_GLOBAL_COMMANDS_COMMENT)?;
+    fn build_metadata(&mut self) -> Result<(), RustexError> {
+        self.record_build(String::from(TITLE_PAGE_DESCRIPTION), || self.metadata.build_preamble(self, &self.class._type))
+    }
+
+    fn build_commands(&mut self) -> Result<(), RustexError> {
+        writeln!(self.get_writer(), "{}", USER_GLOBAL_COMMANDS_COMMENT)?;
         for command in &self.commands {
-            command.build(&self)?;
+            self.build_item(command)?;
         }
 
         self.add_blank_line()
@@ -103,26 +344,156 @@ impl Document {
         for item in &mut self.items {
             item.update_indent(&0);
         }
+
+        self.metadata.update_indent(&1);
     }
 
-    fn build_items(&mut self) -> Result<(), Error> {
-        writeln!(&self.file, "{}\n", DEF_BEGIN_DOCUMENT)?;
+    fn build_items(&mut self) -> Result<(), RustexError> {
+        writeln!(self.get_writer(), "{}\n", DEF_BEGIN_DOCUMENT)?;
+        self.record_build(String::from(TITLE_PAGE_DESCRIPTION),
+                          || self.metadata.build_front_matter(self, &self.class._type, &1))?;
 
         for item in &self.items {
-            item.build(&self)?;
+            self.build_item(item.as_ref())?;
         }
 
-        writeln!(&self.file, "{}", DEF_END_DOCUMENT)
+        writeln!(self.get_writer(), "{}", DEF_END_DOCUMENT)?;
+        Ok(())
+    }
+
+    /// Gets the warnings raised by the last build, e.g. `Packages` used with conflicting options.
+    ///
+    /// Example:
+    ///
+    /// ```rust
+    /// # use rustex::*;
+    /// let doc_class = DocumentClass::new(ClassType::Article, vec![]);
+    /// let mut doc = Document::from_writer(Vec::new(), doc_class);
+    /// doc.add_packages(vec![Package::new("geometry", vec!["portrait", "landscape"])]);
+    /// doc.add_item(Text::new("#red{Required packages are added automatically}"));
+    ///
+    /// let latex = doc.render_to_string().unwrap();
+    /// assert!(latex.contains(r"\usepackage{xcolor}"));
+    /// assert_eq!(doc.get_warnings().len(), 1);
+    /// ```
+    pub fn get_warnings(&self) -> &[String] {
+        &self.warnings
     }
 
+    /// Gets the `SourceMap` recorded by the last build.
+    /// It maps the lines of the generated LaTeX file to the `Items` that wrote them.
+    pub fn get_source_map(&self) -> SourceMap {
+        self.source_map.borrow().clone()
+    }
+
+    /// Builds a nested `Item`, records the lines it writes in the `SourceMap`
+    /// and prepends its description to the path of its errors.
     #[doc(hidden)]
-    pub fn add_blank_line(&self) -> Result<(), Error> {
-        writeln!(&self.file, "")
+    pub fn build_item<I: Item + ?Sized>(&self, item: &I) -> Result<(), RustexError> {
+        self.record_build(item.describe(), || item.build(self))
     }
 
+    fn record_build<F>(&self, description: String, build: F) -> Result<(), RustexError>
+    where F: FnOnce() -> Result<(), RustexError> {
+        self.item_path.borrow_mut().push(description.clone());
+        let start_line = self.get_writer().get_line_count() + 1;
+
+        let result = build();
+
+        let end_line = self.get_writer().get_line_count();
+        let path = self.item_path.borrow().clone();
+        self.item_path.borrow_mut().pop();
+        self.source_map.borrow_mut().record(path, start_line, end_line);
+
+        result.map_err(|error| error.within(description))
+    }
+
+    /// Gets the path to write for a file referenced by an `Item`,
+    /// relative to the assets directory if the `Document` has an `AssetManager`.
+    #[doc(hidden)]
+    pub fn get_asset_path(&self, path: &str) -> String {
+        match &self.assets {
+            Some(assets) => assets.get_path(path),
+            None => String::from(path)
+        }
+    }
+
+    /// Checks whether a `Package` is loaded, either added by the user or required by an `Item`.
+    /// Only known once the packages have been written.
     #[doc(hidden)]
-    pub fn get_file(&self) -> &File {
-        &self.file
+    pub fn is_package_loaded(&self, name: &str) -> bool {
+        self.loaded_packages.iter().any(|loaded_package| loaded_package == name)
+    }
+
+    #[doc(hidden)]
+    pub fn add_blank_line(&self) -> Result<(), RustexError> {
+        writeln!(self.get_writer())?;
+        Ok(())
+    }
+
+    #[doc(hidden)]
+    pub fn get_writer(&self) -> RefMut<'_, DocumentWriter<'a>> {
+        self.writer.borrow_mut()
+    }
+}
+
+/// The writer of a `Document`, which counts the written lines for the `SourceMap`.
+#[doc(hidden)]
+pub struct DocumentWriter<'a> {
+    writer: Box<dyn Write + 'a>,
+    line_count: usize
+}
+
+impl<'a> DocumentWriter<'a> {
+    fn new(writer: Box<dyn Write + 'a>) -> Self {
+        Self {
+            writer,
+            line_count: 0
+        }
+    }
+
+    pub fn get_line_count(&self) -> usize {
+        self.line_count
+    }
+
+    fn reset_line_count(&mut self) {
+        self.line_count = 0;
+    }
+}
+
+impl Write for DocumentWriter<'_> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        let written = self.writer.write(buf)?;
+        self.line_count += buf[..written].iter().filter(|byte| **byte == b'\n').count();
+        Ok(written)
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        self.writer.flush()
+    }
+}
+
+/// An in-memory sink shared between a `Document` and `render_to_string`.
+#[derive(Clone, Default)]
+struct SharedBuffer {
+    bytes: Rc<RefCell<Vec<u8>>>
+}
+
+impl SharedBuffer {
+    fn into_string(self) -> Result<String, Error> {
+        let bytes = self.bytes.take();
+        String::from_utf8(bytes).map_err(|error| Error::new(ErrorKind::InvalidData, error))
+    }
+}
+
+impl Write for SharedBuffer {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        self.bytes.borrow_mut().extend_from_slice(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> Result<(), Error> {
+        Ok(())
     }
 }
 
@@ -168,30 +539,121 @@ impl ClassType {
     }
 }
 
-/// An object to import libraries to your LaTeX file.
-pub struct Package {
-    name: String,
-    options: Vec<String>
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::container::*;
 
-impl Package {
-    /// Initializes a new `Package` object
-    pub fn new(_name: &str, _options: Vec<&str>) -> Self {
-        Self {
-            name: String::from(_name),
-            options: _options
-                .iter()
-                .map(|&option| String::from(option))
-                .collect()
-        }
+    fn find_line(latex: &str, pattern: &str) -> usize {
+        latex.lines().position(|line| line == pattern).unwrap_or_else(|| panic!("'{}' not found in:\n{}", pattern, latex))
     }
 
-    #[doc(hidden)]
-    pub fn build(&self, doc: &Document) -> Result<(), Error> {
-        let options_str = self.options.join(", ");
-        let mut package_str: String = format!("{}{}", DEF_PACKAGE, into_brackets(&options_str));
-        package_str = format!("{}{}", package_str, into_braces(&self.name));
+    #[test]
+    fn loads_late_packages_after_user_packages() {
+        let mut doc = Document::from_writer(Vec::new(), DocumentClass::new(ClassType::Article, vec![]));
+        doc.add_packages(vec![
+            Package::new("babel", vec!["english"]),
+            Package::new("geometry", vec!["margin=2.5cm"])
+        ]);
+
+        let section = Section::new("Results", SectionType::Section, true, "sec_results");
+        let reference = AutoRef::new(&section.get_label());
+        doc.add_item(section);
+        doc.add_item(Text::new(&format!("#red{{See}} {}.", reference)));
+
+        let latex = doc.render_to_string().unwrap();
+        let xcolor_line = find_line(&latex, r"\usepackage{xcolor}");
+        let babel_line = find_line(&latex, r"\usepackage[english]{babel}");
+        let geometry_line = find_line(&latex, r"\usepackage[margin=2.5cm]{geometry}");
+        let hyperref_line = find_line(&latex, r"\usepackage{hyperref}");
+
+        assert!(xcolor_line < babel_line);
+        assert!(babel_line < geometry_line);
+        assert!(geometry_line < hyperref_line);
+        assert!(latex.contains(&format!("{}\n{}", LATE_PACKAGES_COMMENT, r"\usepackage{hyperref}")));
+    }
+
+    #[test]
+    fn keeps_added_late_packages_in_place() {
+        let mut doc = Document::from_writer(Vec::new(), DocumentClass::new(ClassType::Article, vec![]));
+        doc.add_packages(vec![
+            Package::new("hyperref", vec!["hidelinks"]),
+            Package::new("babel", vec!["english"])
+        ]);
+
+        let section = Section::new("Results", SectionType::Section, true, "sec_results");
+        doc.add_item(Text::new(&AutoRef::new(&section.get_label()).to_string()));
+        doc.add_item(section);
+
+        let latex = doc.render_to_string().unwrap();
+
+        assert!(find_line(&latex, r"\usepackage[hidelinks]{hyperref}") < find_line(&latex, r"\usepackage[english]{babel}"));
+        assert!(!latex.contains(LATE_PACKAGES_COMMENT));
+    }
+
+    fn unnumbered_section_in_toc() -> Section {
+        let mut section = Section::new("Acknowledgements", SectionType::Section, false, "sec_thanks");
+        section.set_in_toc(true);
+        section
+    }
+
+    #[test]
+    fn anchors_contents_lines_with_hyperref() {
+        let mut doc = Document::from_writer(Vec::new(), DocumentClass::new(ClassType::Article, vec![]));
+        let section = unnumbered_section_in_toc();
+        doc.add_item(Text::new(&AutoRef::new(&section.get_label()).to_string()));
+        doc.add_item(section);
+
+        let latex = doc.render_to_string().unwrap();
+        let phantom_line = find_line(&latex, "\t\\phantomsection");
+
+        assert_eq!(phantom_line + 1, find_line(&latex, "\t\\addcontentsline{toc}{section}{Acknowledgements}"));
+
+        let mut doc = Document::from_writer(Vec::new(), DocumentClass::new(ClassType::Article, vec![]));
+        doc.add_packages(vec![Package::new("hyperref", vec![])]);
+        doc.add_item(unnumbered_section_in_toc());
+
+        assert!(doc.render_to_string().unwrap().contains(r"\phantomsection"));
+    }
+
+    #[test]
+    fn writes_contents_lines_without_anchor_without_hyperref() {
+        let mut doc = Document::from_writer(Vec::new(), DocumentClass::new(ClassType::Article, vec![]));
+        doc.add_item(unnumbered_section_in_toc());
+
+        let latex = doc.render_to_string().unwrap();
+
+        assert!(latex.contains(r"\addcontentsline{toc}{section}{Acknowledgements}"));
+        assert!(!latex.contains(r"\phantomsection"));
+    }
+
+    #[test]
+    fn loads_the_packages_of_list_labels() {
+        let section = Section::new("Results", SectionType::Section, true, "sec_results");
+        let mut list = Itemize::new();
+        list.add_labeled_item(Text::new(&format!("#red{{!}} {}", AutoRef::new(&section.get_label()))),
+                              Text::new("Important"));
+
+        let mut doc = Document::from_writer(Vec::new(), DocumentClass::new(ClassType::Article, vec![]));
+        doc.add_item(section);
+        doc.add_item(list);
+
+        let latex = doc.render_to_string().unwrap();
+        assert!(latex.lines().any(|line| line == r"\usepackage{xcolor}"), "{}", latex);
+        assert!(latex.lines().any(|line| line == r"\usepackage{hyperref}"), "{}", latex);
+    }
+
+    #[test]
+    fn validates_the_title_page_before_writing() {
+        let mut latex = Vec::new();
+
+        let mut doc = Document::from_writer(&mut latex, DocumentClass::new(ClassType::Letter, vec![]));
+        doc.set_title("Offer");
+        doc.set_subtitle("Terms");
 
-        writeln!(&doc.file, "{}", package_str)
+        assert_eq!(doc.build().unwrap_err().to_string(),
+                   format!("{}: validation error: a subtitle is not supported by the letter class", TITLE_PAGE_DESCRIPTION));
+        drop(doc);
+        assert!(latex.is_empty());
     }
 }
@@ -0,0 +1,467 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use crate::utilities::def_syntax::*;
+use crate::utilities::format::*;
+
+const GREEK_LETTERS: [&str; 39] = [
+    "alpha", "beta", "gamma", "delta", "epsilon", "varepsilon", "zeta", "eta", "theta", "vartheta",
+    "iota", "kappa", "lambda", "mu", "nu", "xi", "pi", "rho", "varrho", "sigma", "varsigma", "tau",
+    "upsilon", "phi", "varphi", "chi", "psi", "omega",
+    "Gamma", "Delta", "Theta", "Lambda", "Xi", "Pi", "Sigma", "Upsilon", "Phi", "Psi", "Omega"
+];
+
+const MATH_FUNCTIONS: [&str; 19] = [
+    "sin", "cos", "tan", "cot", "sec", "csc", "arcsin", "arccos", "arctan",
+    "sinh", "cosh", "tanh", "exp", "log", "ln", "lg", "det", "min", "max"
+];
+
+// Operator precedence, from the loosest to the tightest
+const RELATION_PRECEDENCE: u8 = 0;
+const SUM_PRECEDENCE: u8 = 1;
+const PRODUCT_PRECEDENCE: u8 = 2;
+const SCRIPT_PRECEDENCE: u8 = 3;
+const ATOM_PRECEDENCE: u8 = 4;
+
+/// Defines the delimiters of a matrix.
+#[derive(Clone, Debug)]
+pub enum MatrixStyle {
+    Plain,
+    Parentheses,
+    Brackets,
+    Braces,
+    Bars,
+    DoubleBars
+}
+
+impl MatrixStyle {
+    pub fn get_environment(&self) -> &str {
+        match self {
+            MatrixStyle::Plain => MATRIX_ENVIRONMENT,
+            MatrixStyle::Parentheses => PARENTHESES_MATRIX_ENVIRONMENT,
+            MatrixStyle::Brackets => BRACKETS_MATRIX_ENVIRONMENT,
+            MatrixStyle::Braces => BRACES_MATRIX_ENVIRONMENT,
+            MatrixStyle::Bars => BARS_MATRIX_ENVIRONMENT,
+            MatrixStyle::DoubleBars => DOUBLE_BARS_MATRIX_ENVIRONMENT
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Node {
+    Symbol(String),
+    Number(String),
+    Group(Box<MathExpr>),
+    Function(String, Box<MathExpr>),
+    Fraction(Box<MathExpr>, Box<MathExpr>),
+    Root(Option<Box<MathExpr>>, Box<MathExpr>),
+    Subscript(Box<MathExpr>, Box<MathExpr>),
+    Superscript(Box<MathExpr>, Box<MathExpr>),
+    BigOperator(&'static str, Box<MathExpr>, Box<MathExpr>, Box<MathExpr>),
+    Integral(Box<MathExpr>, Box<MathExpr>, Box<MathExpr>, Box<MathExpr>),
+    Matrix(MatrixStyle, Vec<Vec<MathExpr>>),
+    Negation(Box<MathExpr>),
+    Binary(&'static str, u8, Box<MathExpr>, Box<MathExpr>)
+}
+
+/// A math formula, built with the functions of the `math` module, such as `sym`, `frac` or `sum`,
+/// and the `+ - * /` operators.
+/// Symbols are escaped and every argument is braced, so the generated LaTeX is always balanced.
+/// Parentheses are added where the precedence of the operators requires them.
+///
+/// A `MathExpr` can be written in a `Text` with `to_inline`,
+/// or displayed with `Equation::from_expression`, `Align::add_expression` and `Gather::add_expression`.
+///
+/// Example:
+///
+/// ```rust
+/// # use rustex::*;
+/// use rustex::math::*;
+///
+/// let mean = equals(sub("x", "mean"), frac(1, "n") * sum("i", 1, "n", sub("x", "i")));
+/// assert_eq!(mean.to_latex(), r"x_{\mathit{mean}} = \frac{1}{n} \cdot \sum_{i = 1}^{n} x_{i}");
+///
+/// let rotation = matrix(MatrixStyle::Parentheses, vec![
+///     vec![func("cos", "theta"), -func("sin", "theta")],
+///     vec![func("sin", "theta"), func("cos", "theta")]
+/// ]);
+/// assert_eq!(rotation.to_latex(),
+///            r"\begin{pmatrix} \cos\left(\theta\right) & -\sin\left(\theta\right) \\ \sin\left(\theta\right) & \cos\left(\theta\right) \end{pmatrix}");
+///
+/// let text = Text::new(&format!("The area is {}.", (sym("pi") * sup("r", 2)).to_inline()));
+/// assert_eq!(text.get_string(), r"The area is $\pi \cdot r^{2}$.");
+/// ```
+#[derive(Clone, Debug)]
+pub struct MathExpr {
+    node: Node
+}
+
+impl MathExpr {
+    fn new(node: Node) -> Self {
+        Self { node }
+    }
+
+    /// Gets the LaTeX code of the `MathExpr`, without math delimiters.
+    pub fn to_latex(&self) -> String {
+        match &self.node {
+            Node::Symbol(symbol) => symbol.clone(),
+            Node::Number(number) => number.clone(),
+            Node::Group(expression) => into_parentheses(&expression.to_latex()),
+            Node::Function(name, argument) => format!("{}{}", name, into_parentheses(&argument.to_latex())),
+            Node::Fraction(numerator, denominator) => {
+                format!("{}{}{}", DEF_FRACTION, into_braces(&numerator.to_latex()), into_braces(&denominator.to_latex()))
+            }
+            Node::Root(degree, radicand) => match degree {
+                Some(degree) => {
+                    format!("{}{}{}", DEF_SQUARE_ROOT, into_brackets(&degree.to_latex()), into_braces(&radicand.to_latex()))
+                }
+                None => format!("{}{}", DEF_SQUARE_ROOT, into_braces(&radicand.to_latex()))
+            },
+            Node::Subscript(base, index) => format!("{}_{}", base.render_script_base(), into_braces(&index.to_latex())),
+            Node::Superscript(base, exponent) => {
+                let base_str = match &base.node {
+                    Node::Subscript(_, _) => base.to_latex(),
+                    _ => base.render_script_base()
+                };
+
+                format!("{}^{}", base_str, into_braces(&exponent.to_latex()))
+            }
+            Node::BigOperator(operator, lower, upper, body) => {
+                format!("{}_{}^{} {}", operator, into_braces(&lower.to_latex()), into_braces(&upper.to_latex()),
+                        body.render_operand(PRODUCT_PRECEDENCE))
+            }
+            Node::Integral(lower, upper, body, variable) => {
+                format!("{}_{}^{} {} {}{}", DEF_INTEGRAL, into_braces(&lower.to_latex()), into_braces(&upper.to_latex()),
+                        body.render_operand(PRODUCT_PRECEDENCE), DEF_DIFFERENTIAL, variable.render_script_base())
+            }
+            Node::Matrix(style, rows) => {
+                let rows_str: Vec<String> = rows
+                    .iter()
+                    .map(|row| row.iter().map(|cell| cell.to_latex()).collect::<Vec<String>>().join(" & "))
+                    .collect();
+
+                let environment = String::from(style.get_environment());
+                format!("{}{} {} {}{}", DEF_BEGIN, into_braces(&environment),
+                        rows_str.join(&format!(" {} ", DEF_LINE_BREAK)), DEF_END, into_braces(&environment))
+            }
+            Node::Negation(operand) => format!("-{}", operand.render_operand(PRODUCT_PRECEDENCE)),
+            Node::Binary(operator, precedence, left, right) => render_binary(operator, *precedence, left, right)
+        }
+    }
+
+    /// Gets the LaTeX code of the `MathExpr` as inline math (`$...$`), to be written in a `Text`.
+    pub fn to_inline(&self) -> String {
+        format!("${}$", self.to_latex())
+    }
+
+    // The relation of an aligned line is preceded by the alignment point, e.g. `f(x) &= x^2`
+    pub(crate) fn to_aligned_latex(&self) -> String {
+        match &self.node {
+            Node::Binary(operator, RELATION_PRECEDENCE, left, right) => {
+                render_binary(&format!("&{}", operator), RELATION_PRECEDENCE, left, right)
+            }
+            _ => self.to_latex()
+        }
+    }
+
+    fn precedence(&self) -> u8 {
+        match &self.node {
+            Node::Binary(_, precedence, _, _) => *precedence,
+            Node::BigOperator(_, _, _, _) | Node::Integral(_, _, _, _) => PRODUCT_PRECEDENCE,
+            Node::Negation(_) => SUM_PRECEDENCE,
+            Node::Subscript(_, _) | Node::Superscript(_, _) => SCRIPT_PRECEDENCE,
+            _ => ATOM_PRECEDENCE
+        }
+    }
+
+    fn render_operand(&self, min_precedence: u8) -> String {
+        match self.precedence() >= min_precedence {
+            true => self.to_latex(),
+            false => into_parentheses(&self.to_latex())
+        }
+    }
+
+    fn render_script_base(&self) -> String {
+        self.render_operand(ATOM_PRECEDENCE)
+    }
+
+    fn binary(operator: &'static str, precedence: u8, left: MathExpr, right: MathExpr) -> Self {
+        Self::new(Node::Binary(operator, precedence, Box::new(left), Box::new(right)))
+    }
+}
+
+impl fmt::Display for MathExpr {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_latex())
+    }
+}
+
+impl From<&str> for MathExpr {
+    fn from(symbol: &str) -> Self {
+        sym(symbol)
+    }
+}
+
+macro_rules! impl_from_number {
+    ($($number:ty),*) => {
+        $(
+            impl From<$number> for MathExpr {
+                fn from(number: $number) -> Self {
+                    num(number)
+                }
+            }
+        )*
+    };
+}
+
+impl_from_number!(i32, i64, u32, u64, usize, f32, f64);
+
+impl<E: Into<MathExpr>> Add<E> for MathExpr {
+    type Output = MathExpr;
+
+    fn add(self, right: E) -> MathExpr {
+        MathExpr::binary("+", SUM_PRECEDENCE, self, right.into())
+    }
+}
+
+impl<E: Into<MathExpr>> Sub<E> for MathExpr {
+    type Output = MathExpr;
+
+    fn sub(self, right: E) -> MathExpr {
+        MathExpr::binary("-", SUM_PRECEDENCE, self, right.into())
+    }
+}
+
+impl<E: Into<MathExpr>> Mul<E> for MathExpr {
+    type Output = MathExpr;
+
+    fn mul(self, right: E) -> MathExpr {
+        MathExpr::binary(DEF_CDOT, PRODUCT_PRECEDENCE, self, right.into())
+    }
+}
+
+impl<E: Into<MathExpr>> Div<E> for MathExpr {
+    type Output = MathExpr;
+
+    fn div(self, right: E) -> MathExpr {
+        frac(self, right)
+    }
+}
+
+impl Neg for MathExpr {
+    type Output = MathExpr;
+
+    fn neg(self) -> MathExpr {
+        MathExpr::new(Node::Negation(Box::new(self)))
+    }
+}
+
+// Products are associative, and a sum or an integral extends to the end of a product,
+// so it only needs parentheses on the left of the product
+fn render_binary(operator: &str, precedence: u8, left: &MathExpr, right: &MathExpr) -> String {
+    let is_left_big_operator = matches!(left.node, Node::BigOperator(_, _, _, _) | Node::Integral(_, _, _, _));
+
+    let left_str = match is_left_big_operator && precedence >= PRODUCT_PRECEDENCE {
+        true => into_parentheses(&left.to_latex()),
+        false => left.render_operand(precedence)
+    };
+
+    let right_str = match precedence {
+        PRODUCT_PRECEDENCE => right.render_operand(precedence),
+        _ => right.render_operand(precedence + 1)
+    };
+
+    format!("{} {} {}", left_str, operator, right_str)
+}
+
+fn into_parentheses(string: &str) -> String {
+    format!("{}{}{}", DEF_LEFT_PARENTHESIS, string, DEF_RIGHT_PARENTHESIS)
+}
+
+// Symbols are written in math mode, where text escapes such as `\textasciitilde` are not defined
+fn escape_symbol(symbol: &str) -> String {
+    let mut escaped_symbol = String::with_capacity(symbol.len());
+
+    for character in symbol.chars() {
+        match character {
+            '%' | '&' | '$' | '#' | '_' | '{' | '}' => {
+                escaped_symbol.push('\\');
+                escaped_symbol.push(character);
+            }
+            '~' => escaped_symbol.push_str(DEF_MATH_TILDE),
+            '^' => escaped_symbol.push_str(DEF_MATH_CIRCUMFLEX),
+            '\\' => escaped_symbol.push_str(DEF_MATH_BACKSLASH),
+            _ => escaped_symbol.push(character)
+        }
+    }
+
+    escaped_symbol
+}
+
+/// A symbol, e.g. `x`.
+/// Greek letter names (`alpha`, `Delta`, `varphi`, etc.) are written as Greek letters,
+/// and other names longer than one character are written in italic (`\mathit`).
+pub fn sym(name: &str) -> MathExpr {
+    let symbol = match name {
+        _ if GREEK_LETTERS.contains(&name) => format!("\\{}", name),
+        _ if name.chars().count() > 1 => format!("{}{}", DEF_MATH_ITALIC, into_braces(&escape_symbol(name))),
+        _ => escape_symbol(name)
+    };
+
+    MathExpr::new(Node::Symbol(symbol))
+}
+
+/// A number, e.g. `42` or `3.14`.
+/// Negative numbers are written in parentheses where needed.
+/// Infinite floats are written as `\infty`, and `NaN` as `\text{NaN}` (`amsmath`).
+pub fn num<N: fmt::Display>(value: N) -> MathExpr {
+    let number = value.to_string();
+
+    match number.strip_prefix('-') {
+        Some(absolute_value) => -positive_num(absolute_value),
+        None => positive_num(&number)
+    }
+}
+
+fn positive_num(number: &str) -> MathExpr {
+    match number {
+        POSITIVE_INFINITY => infinity(),
+        NOT_A_NUMBER => MathExpr::new(Node::Symbol(format!("{}{}", DEF_MATH_TEXT, into_braces(&String::from(NOT_A_NUMBER))))),
+        _ => MathExpr::new(Node::Number(escape_symbol(number)))
+    }
+}
+
+/// The infinity symbol (`\infty`).
+pub fn infinity() -> MathExpr {
+    MathExpr::new(Node::Symbol(String::from(DEF_INFINITY)))
+}
+
+/// An expression in parentheses.
+pub fn paren<E: Into<MathExpr>>(expression: E) -> MathExpr {
+    MathExpr::new(Node::Group(Box::new(expression.into())))
+}
+
+/// A function applied to an argument, e.g. `\sin\left(x\right)`.
+/// Common functions (`sin`, `log`, `exp`, etc.) use their LaTeX command,
+/// other names longer than one character are written with `\operatorname` (`amsmath`).
+pub fn func<E: Into<MathExpr>>(name: &str, argument: E) -> MathExpr {
+    let name_str = match name {
+        _ if MATH_FUNCTIONS.contains(&name) => format!("\\{}", name),
+        _ if name.chars().count() > 1 => format!("{}{}", DEF_OPERATOR_NAME, into_braces(&escape_symbol(name))),
+        _ => escape_symbol(name)
+    };
+
+    MathExpr::new(Node::Function(name_str, Box::new(argument.into())))
+}
+
+/// A fraction (`\frac`).
+pub fn frac<N: Into<MathExpr>, D: Into<MathExpr>>(numerator: N, denominator: D) -> MathExpr {
+    MathExpr::new(Node::Fraction(Box::new(numerator.into()), Box::new(denominator.into())))
+}
+
+/// A square root (`\sqrt`).
+pub fn sqrt<E: Into<MathExpr>>(radicand: E) -> MathExpr {
+    MathExpr::new(Node::Root(None, Box::new(radicand.into())))
+}
+
+/// A root of any degree, e.g. `\sqrt[3]{x}`.
+pub fn root<D: Into<MathExpr>, E: Into<MathExpr>>(degree: D, radicand: E) -> MathExpr {
+    MathExpr::new(Node::Root(Some(Box::new(degree.into())), Box::new(radicand.into())))
+}
+
+/// A subscript, e.g. `x_{i}`.
+pub fn sub<B: Into<MathExpr>, I: Into<MathExpr>>(base: B, index: I) -> MathExpr {
+    MathExpr::new(Node::Subscript(Box::new(base.into()), Box::new(index.into())))
+}
+
+/// A superscript, e.g. `x^{2}`.
+pub fn sup<B: Into<MathExpr>, E: Into<MathExpr>>(base: B, exponent: E) -> MathExpr {
+    MathExpr::new(Node::Superscript(Box::new(base.into()), Box::new(exponent.into())))
+}
+
+/// A sum over an index, e.g. `\sum_{i = 0}^{n} x_{i}`.
+pub fn sum<I, F, T, B>(index: I, from: F, to: T, body: B) -> MathExpr
+    where I: Into<MathExpr>, F: Into<MathExpr>, T: Into<MathExpr>, B: Into<MathExpr> {
+    big_operator(DEF_SUM, index.into(), from.into(), to.into(), body.into())
+}
+
+/// A product over an index, e.g. `\prod_{i = 0}^{n} x_{i}`.
+pub fn prod<I, F, T, B>(index: I, from: F, to: T, body: B) -> MathExpr
+    where I: Into<MathExpr>, F: Into<MathExpr>, T: Into<MathExpr>, B: Into<MathExpr> {
+    big_operator(DEF_PRODUCT, index.into(), from.into(), to.into(), body.into())
+}
+
+/// A definite integral, e.g. `\int_{0}^{1} x \,dx`.
+pub fn integral<F, T, B, V>(from: F, to: T, body: B, variable: V) -> MathExpr
+    where F: Into<MathExpr>, T: Into<MathExpr>, B: Into<MathExpr>, V: Into<MathExpr> {
+    MathExpr::new(Node::Integral(Box::new(from.into()), Box::new(to.into()),
+                                 Box::new(body.into()), Box::new(variable.into())))
+}
+
+fn big_operator(operator: &'static str, index: MathExpr, from: MathExpr, to: MathExpr, body: MathExpr) -> MathExpr {
+    let lower = equals(index, from);
+    MathExpr::new(Node::BigOperator(operator, Box::new(lower), Box::new(to), Box::new(body)))
+}
+
+/// A matrix (`amsmath`), given by rows.
+pub fn matrix(style: MatrixStyle, rows: Vec<Vec<MathExpr>>) -> MathExpr {
+    MathExpr::new(Node::Matrix(style, rows))
+}
+
+/// A product written with a cross (`\times`), e.g. for dimensions.
+pub fn times<L: Into<MathExpr>, R: Into<MathExpr>>(left: L, right: R) -> MathExpr {
+    MathExpr::binary(DEF_TIMES, PRODUCT_PRECEDENCE, left.into(), right.into())
+}
+
+/// A value with an uncertainty (`\pm`).
+pub fn plus_minus<L: Into<MathExpr>, R: Into<MathExpr>>(left: L, right: R) -> MathExpr {
+    MathExpr::binary(DEF_PLUS_MINUS, SUM_PRECEDENCE, left.into(), right.into())
+}
+
+macro_rules! relation {
+    ($(#[$doc:meta] $name:ident => $operator:expr),*) => {
+        $(
+            #[$doc]
+            pub fn $name<L: Into<MathExpr>, R: Into<MathExpr>>(left: L, right: R) -> MathExpr {
+                MathExpr::binary($operator, RELATION_PRECEDENCE, left.into(), right.into())
+            }
+        )*
+    };
+}
+
+relation!(
+    /// An equality (`=`).
+    equals => "=",
+    /// An inequality (`\neq`).
+    not_equals => DEF_NOT_EQUALS,
+    /// A strict inequality (`<`).
+    less_than => "<",
+    /// An inequality (`\leq`).
+    less_or_equal => DEF_LESS_OR_EQUAL,
+    /// A strict inequality (`>`).
+    greater_than => ">",
+    /// An inequality (`\geq`).
+    greater_or_equal => DEF_GREATER_OR_EQUAL,
+    /// An approximation (`\approx`).
+    approx => DEF_APPROX
+);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_non_finite_numbers() {
+        assert_eq!(num(f64::INFINITY).to_latex(), r"\infty");
+        assert_eq!(num(f32::NEG_INFINITY).to_latex(), r"-\infty");
+        assert_eq!(num(f64::NAN).to_latex(), r"\text{NaN}");
+        assert_eq!((sym("x") - f64::NEG_INFINITY).to_latex(), r"x - \left(-\infty\right)");
+    }
+
+    #[test]
+    fn clones_expressions() {
+        let radius = sup("r", 2);
+        let area = sym("pi") * radius.clone();
+        assert_eq!(radius.to_latex(), "r^{2}");
+        assert_eq!(area.to_latex(), r"\pi \cdot r^{2}");
+    }
+}
@@ -0,0 +1,281 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use crate::compile::Engine;
+use crate::error::RustexError;
+use crate::utilities::def_syntax::*;
+
+/// A placement specifier of a float, such as a `Figure`.
+/// Refer to `figure` in LaTeX documentation for more information.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Placement {
+    /// `h`: at the position in the text, if possible.
+    Here,
+    /// `t`: at the top of a page.
+    Top,
+    /// `b`: at the bottom of a page.
+    Bottom,
+    /// `p`: on a separate page for floats.
+    Page,
+    /// `!`: overrides the LaTeX rules that limit the number of floats.
+    Force,
+    /// `H`: exactly at the position in the text (`float` package). Cannot be combined.
+    HereDefinitely
+}
+
+impl Placement {
+    pub fn get_def(&self) -> &str {
+        match self {
+            Placement::Here => "h",
+            Placement::Top => "t",
+            Placement::Bottom => "b",
+            Placement::Page => "p",
+            Placement::Force => "!",
+            Placement::HereDefinitely => FLOAT_HERE_POSITIONING
+        }
+    }
+}
+
+// `H` is not a placement preference but a different float algorithm, so it stands alone
+pub(crate) fn into_positioning(placements: &[Placement]) -> Result<String, RustexError> {
+    let is_combined = placements.iter().any(|placement| *placement != Placement::HereDefinitely);
+
+    if placements.contains(&Placement::HereDefinitely) && is_combined {
+        return Err(RustexError::validation("placement 'H' cannot be combined with other placements"));
+    }
+
+    // `!` comes first, and every other placement is written once, in the given order
+    let mut sorted_placements: Vec<Placement> = Vec::new();
+
+    for placement in placements {
+        if !sorted_placements.contains(placement) {
+            sorted_placements.push(*placement);
+        }
+    }
+
+    sorted_placements.sort_by_key(|placement| *placement != Placement::Force);
+    Ok(sorted_placements.iter().map(|placement| placement.get_def()).collect())
+}
+
+/// A LaTeX length, e.g. `3cm` or `0.5\textwidth`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Length {
+    Cm(f64),
+    Mm(f64),
+    In(f64),
+    Pt(f64),
+    Em(f64),
+    /// A fraction of the width of the text.
+    TextWidth(f64),
+    /// A fraction of the width of the current line, e.g. inside a `SubFigure`.
+    LineWidth(f64),
+    /// A fraction of the height of the text.
+    TextHeight(f64)
+}
+
+impl fmt::Display for Length {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Length::Cm(value) => write!(f, "{}cm", value),
+            Length::Mm(value) => write!(f, "{}mm", value),
+            Length::In(value) => write!(f, "{}in", value),
+            Length::Pt(value) => write!(f, "{}pt", value),
+            Length::Em(value) => write!(f, "{}em", value),
+            Length::TextWidth(value) => write!(f, "{}{}", value, DEF_TEXT_WIDTH),
+            Length::LineWidth(value) => write!(f, "{}{}", value, DEF_LINE_WIDTH),
+            Length::TextHeight(value) => write!(f, "{}{}", value, DEF_TEXT_HEIGHT)
+        }
+    }
+}
+
+/// The options of an image (`\includegraphics`).
+/// Refer to the `graphicx` package documentation for more information.
+///
+/// Example:
+///
+/// ```rust
+/// # use rustex::*;
+/// let options = GraphicsOptions::new()
+///     .width(Length::TextWidth(0.8))
+///     .angle(90.0)
+///     .trim(Length::Cm(1.0), Length::Cm(0.0), Length::Cm(1.0), Length::Cm(0.5))
+///     .clip(true);
+///
+/// assert_eq!(options.to_string(), r"width=0.8\textwidth, angle=90, trim=1cm 0cm 1cm 0.5cm, clip");
+///
+/// let subfigure = SubFigure::new("plot.png", &options.to_string(), None, "fig_plot");
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct GraphicsOptions {
+    width: Option<Length>,
+    height: Option<Length>,
+    scale: Option<f64>,
+    angle: Option<f64>,
+    trim: Option<[Length; 4]>,
+    clip: bool,
+    keep_aspect_ratio: bool
+}
+
+impl GraphicsOptions {
+    /// Initializes a new `GraphicsOptions` object, without any option
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Scales the image to the given width.
+    pub fn width(mut self, width: Length) -> Self {
+        self.width = Some(width);
+        self
+    }
+
+    /// Scales the image to the given height.
+    pub fn height(mut self, height: Length) -> Self {
+        self.height = Some(height);
+        self
+    }
+
+    /// Scales the image by a factor, e.g. `0.5` for half its size.
+    pub fn scale(mut self, scale: f64) -> Self {
+        self.scale = Some(scale);
+        self
+    }
+
+    /// Rotates the image counterclockwise, in degrees.
+    pub fn angle(mut self, angle: f64) -> Self {
+        self.angle = Some(angle);
+        self
+    }
+
+    /// Trims the image by the given lengths. The trimmed parts are only hidden with `clip`.
+    pub fn trim(mut self, left: Length, bottom: Length, right: Length, top: Length) -> Self {
+        self.trim = Some([left, bottom, right, top]);
+        self
+    }
+
+    /// Hides the parts of the image outside of its bounding box, e.g. the parts removed by `trim`.
+    pub fn clip(mut self, clip: bool) -> Self {
+        self.clip = clip;
+        self
+    }
+
+    /// Keeps the aspect ratio of the image when both its width and its height are set.
+    pub fn keep_aspect_ratio(mut self, keep_aspect_ratio: bool) -> Self {
+        self.keep_aspect_ratio = keep_aspect_ratio;
+        self
+    }
+}
+
+impl fmt::Display for GraphicsOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut options: Vec<String> = Vec::new();
+
+        if let Some(width) = &self.width {
+            options.push(format!("width={}", width));
+        }
+
+        if let Some(height) = &self.height {
+            options.push(format!("height={}", height));
+        }
+
+        if self.keep_aspect_ratio {
+            options.push(String::from("keepaspectratio"));
+        }
+
+        if let Some(scale) = &self.scale {
+            options.push(format!("scale={}", scale));
+        }
+
+        if let Some(angle) = &self.angle {
+            options.push(format!("angle={}", angle));
+        }
+
+        if let Some(trim) = &self.trim {
+            let lengths: Vec<String> = trim.iter().map(|length| length.to_string()).collect();
+            options.push(format!("trim={}", lengths.join(" ")));
+        }
+
+        if self.clip {
+            options.push(String::from("clip"));
+        }
+
+        write!(f, "{}", options.join(", "))
+    }
+}
+
+/// Defines whether the caption of a float is written above or below its content.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CaptionPosition {
+    Above,
+    Below
+}
+
+// Like `\includegraphics`, an image without extension is looked up with every supported extension
+pub(crate) fn resolve_image(path: &Path, formats: &[&str]) -> Option<PathBuf> {
+    match path.extension() {
+        Some(_) => Some(path.to_path_buf()).filter(|path| path.is_file()),
+        None => formats.iter().map(|format| path.with_extension(format)).find(|path| path.is_file())
+    }
+}
+
+/// A check that an image exists and has a format supported by an `Engine`.
+#[derive(Clone, Debug)]
+pub(crate) struct ImageCheck {
+    engine: Engine,
+    base_dir: PathBuf
+}
+
+impl ImageCheck {
+    pub(crate) fn new(engine: Engine, base_dir: &Path) -> Self {
+        Self {
+            engine,
+            base_dir: base_dir.to_path_buf()
+        }
+    }
+
+    pub(crate) fn check(&self, image_path: &str) -> Result<(), RustexError> {
+        let path = self.base_dir.join(image_path);
+        let formats = self.engine.get_image_formats();
+
+        if let Some(extension) = path.extension() {
+            let extension = extension.to_string_lossy().to_lowercase();
+
+            if !formats.contains(&extension.as_str()) {
+                return Err(RustexError::validation(&format!(
+                    "image format '{}' of '{}' is not supported by {} (supported formats: {})",
+                    extension, image_path, self.engine.get_command(), formats.join(", ")
+                )));
+            }
+        }
+
+        match resolve_image(&path, formats) {
+            Some(_) => Ok(()),
+            None if path.extension().is_some() => {
+                Err(RustexError::validation(&format!("image '{}' does not exist", path.display())))
+            }
+            None => Err(RustexError::validation(&format!(
+                "image '{}' does not exist with any of the extensions {}", path.display(), formats.join(", ")
+            )))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn writes_each_placement_once() {
+        let placements = [Placement::Here, Placement::Top, Placement::Here, Placement::Force, Placement::Top];
+        assert_eq!(into_positioning(&placements).unwrap(), "!ht");
+        assert_eq!(into_positioning(&[Placement::Bottom, Placement::Page]).unwrap(), "bp");
+        assert_eq!(into_positioning(&[]).unwrap(), "");
+    }
+
+    #[test]
+    fn writes_here_definitely_alone() {
+        let placements = [Placement::HereDefinitely, Placement::HereDefinitely];
+        assert_eq!(into_positioning(&placements).unwrap(), "H");
+
+        let error = into_positioning(&[Placement::HereDefinitely, Placement::Top]).unwrap_err();
+        assert_eq!(error.to_string(), "validation error: placement 'H' cannot be combined with other placements");
+    }
+}
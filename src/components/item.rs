@@ -1,13 +1,71 @@
-use std::io::Error;
+use std::path::Path;
+use super::asset::*;
 use super::document::*;
+use super::graphics::*;
+use super::package::*;
+use super::reference::*;
+use crate::compile::Engine;
+use crate::error::RustexError;
 use crate::utilities::format::*;
+use crate::utilities::markup::*;
 use crate::utilities::def_syntax::*;
 
 
 /// A component that can be added to a `Document` or a `Container`
 pub trait Item {
-    fn build(&self, doc: &Document) -> Result<(), Error>;
+    fn build(&self, doc: &Document) -> Result<(), RustexError>;
     fn update_indent(&mut self, super_indent: &usize);
+
+    /// Describes the `Item` in the path of a `RustexError`, e.g. `Section 'Data'`.
+    /// Defaults to the name of the type, without its module path.
+    fn describe(&self) -> String {
+        type_short_name(std::any::type_name::<Self>())
+    }
+
+    /// Lists the `Packages` needed by this `Item` and its nested `Items`.
+    fn required_packages(&self) -> Vec<Package> {
+        Vec::new()
+    }
+
+    /// Registers the `Labels` defined by this `Item` and its nested `Items`.
+    fn register_labels(&self, _registry: &mut LabelRegistry) -> Result<(), RustexError> {
+        Ok(())
+    }
+
+    /// Checks that the `Labels` referenced by this `Item` and its nested `Items` are defined.
+    fn check_references(&self, _registry: &LabelRegistry) -> Result<(), RustexError> {
+        Ok(())
+    }
+
+    /// Checks the structure of this `Item` and its nested `Items`, e.g. the widths of the rows of a `Table`.
+    /// Runs before anything is written, so an invalid `Document` leaves no partial output.
+    fn validate(&self) -> Result<(), RustexError> {
+        Ok(())
+    }
+
+    /// Registers the files referenced by this `Item` and its nested `Items`, e.g. images.
+    fn register_assets(&self, _assets: &mut AssetManager) -> Result<(), RustexError> {
+        Ok(())
+    }
+}
+
+// Strips the module paths from a type name, e.g. `my_crate::Chart<my_crate::Point>` into `Chart<Point>`
+fn type_short_name(type_name: &str) -> String {
+    let mut name = String::new();
+    let mut segment = String::new();
+
+    for c in type_name.chars() {
+        if c.is_alphanumeric() || c == '_' || c == ':' {
+            segment.push(c);
+        } else {
+            name.push_str(segment.rsplit("::").next().unwrap_or_default());
+            segment.clear();
+            name.push(c);
+        }
+    }
+
+    name.push_str(segment.rsplit("::").next().unwrap_or_default());
+    name
 }
 
 /// An `Item` that wraps strings.
@@ -15,6 +73,22 @@ pub trait Item {
 /// Supports Markdown **bold** formatting (\*\*bold\*\*)
 /// Supports Markdown _italic_ formatting (\_bold\_)
 /// Supports Color text formatting (#blue{text})
+/// Supports inline math ($x_1 + y^2$)
+/// Supports inline code (\`snake_case\`)
+///
+/// Blank lines separate paragraphs, and a line ending with `\\` or two spaces is followed by a line break.
+/// The indentation of multi-line strings is removed according to the `Dedent` of the `Text`.
+/// A line break at the end of a paragraph is dropped, since it has no line to end.
+/// In a caption, a table cell or a list label, the lines are joined by spaces,
+/// and a paragraph break or a line break is reported as a `RustexError`.
+///
+/// Formatting can be nested (`**bold _italic_**`, `#red{**text**}`).
+/// Underscores inside words (`snake_case_name`) are not formatted.
+/// Markers can be written literally by escaping them with a backslash (`\*`, `\_`, `\#`, `\$`, `` \` ``).
+/// Inline math is written as is, so `_` and `*` inside `$...$` are not formatted.
+/// A `$` that does not open inline math, such as in `costs $5`, is escaped like any other character.
+/// Inline code is written in a monospace font (`\texttt`), with its special characters escaped and no formatting.
+/// Use a `CodeListing` or a `Verbatim` for blocks of code.
 ///
 /// Here are all the default colors:
 /// - red
@@ -38,48 +112,328 @@ pub trait Item {
 /// - violet
 ///
 /// Other colors can be used by adding `Packages` or defining custom colors.
+///
+/// LaTeX special characters (`% & # _ { } ~ ^ \`) outside of the formatting markup are escaped.
+/// Use `Text::raw` to write LaTeX code as is.
+///
+/// Example:
+///
+/// ```rust
+/// # use rustex::*;
+/// let text = Text::new("**50%** of {users} & _admins_");
+/// assert_eq!(text.get_string(), r"\textbf{50\%} of \{users\} \& \textit{admins}");
+///
+/// let raw_text = Text::raw(r"\multicolumn{3}{|c|}{Lorem Ipsum}");
+/// assert_eq!(raw_text.get_string(), r"\multicolumn{3}{|c|}{Lorem Ipsum}");
+///
+/// let nested_text = Text::new("#red{**x**} in snake_case_name");
+/// assert_eq!(nested_text.get_string(), r"{\color{red}{\textbf{x}}} in snake\_case\_name");
+///
+/// let math_text = Text::new(r"_Energy_ $E_k = m * v^2 / 2$ costs \$5");
+/// assert_eq!(math_text.get_string(), r"\textit{Energy} $E_k = m * v^2 / 2$ costs \$5");
+///
+/// let price_text = Text::new("Between $5 and $10");
+/// assert_eq!(price_text.get_string(), r"Between \$5 and \$10");
+///
+/// let code_text = Text::new("Call `get_user(**id**)` with `50%`");
+/// assert_eq!(code_text.get_string(), r"Call \texttt{get\_user(**id**)} with \texttt{50\%}");
+///
+/// assert!(Text::parse("**unclosed _markup_").is_err());
+/// ```
 pub struct Text {
-    text: String,
+    content: Vec<Inline>,
+    error: Option<MarkupError>,
+    dedent: Dedent,
     indent: usize
 }
 
+/// Defines how the indentation of the lines of a multi-line `Text` is removed.
+///
+/// Example:
+///
+/// ```rust
+/// # use rustex::*;
+/// let mut text = Text::new(
+///     r"First paragraph,\\
+///     with a line break.
+///
+///     Second paragraph:
+///         indented line"
+/// );
+///
+/// text.set_dedent(Dedent::Common);
+/// ```
+///
+/// Generated LaTeX:
+///
+/// ```tex
+/// First paragraph, \\
+/// with a line break.
+///
+/// Second paragraph:
+///     indented line
+/// ```
+pub enum Dedent {
+    /// Keeps the indentation of every line.
+    None,
+    /// Removes the indentation common to every line but the first one,
+    /// which is usually written right after the opening quote of a Rust string literal.
+    Common,
+    /// Removes the indentation of every line.
+    All
+}
+
+impl Dedent {
+    fn apply<'a>(&self, lines: &[&'a str]) -> Vec<&'a str> {
+        match self {
+            Dedent::None => lines.to_vec(),
+            Dedent::All => lines.iter().map(|line| line.trim_start()).collect(),
+            Dedent::Common => {
+                let indented_lines = match lines.len() {
+                    1 => lines,
+                    _ => &lines[1..]
+                };
+
+                // The indentation is compared character by character, so a tab and a space differ
+                let common_indent = indented_lines
+                    .iter()
+                    .filter(|line| !line.trim().is_empty())
+                    .map(|line| leading_indent(line))
+                    .reduce(|common, indent| &common[..shared_prefix_length(common, indent)])
+                    .unwrap_or("");
+
+                lines
+                    .iter()
+                    .map(|line| &line[shared_prefix_length(leading_indent(line), common_indent)..])
+                    .collect()
+            }
+        }
+    }
+}
+
+// The leading spaces and tabs of a line, other whitespace is kept as text
+fn leading_indent(line: &str) -> &str {
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+// Both strings only contain ASCII spaces and tabs, so the length is a char boundary
+fn shared_prefix_length(first: &str, second: &str) -> usize {
+    first
+        .bytes()
+        .zip(second.bytes())
+        .take_while(|(first_byte, second_byte)| first_byte == second_byte)
+        .count()
+}
+
 impl Text {
     /// Initializes a new `Text` object
+    /// Unbalanced markup is reported as a `RustexError` when the `Document` is built.
     pub fn new(_text: &str) -> Self {
+        match parse_markup(_text) {
+            Ok(_content) => Self {
+                content: _content,
+                error: None,
+                dedent: Dedent::Common,
+                indent: 0
+            },
+            Err(_error) => Self {
+                content: vec![Inline::Plain(String::from(_text))],
+                error: Some(_error),
+                dedent: Dedent::Common,
+                indent: 0
+            }
+        }
+    }
+
+    /// Initializes a new `Text` object, or returns an error if its markup is unbalanced.
+    pub fn parse(_text: &str) -> Result<Self, RustexError> {
+        let text = Self::new(_text);
+
+        match &text.error {
+            Some(error) => Err(RustexError::format(&error.to_string())),
+            None => Ok(text)
+        }
+    }
+
+    /// Initializes a new `Text` object containing raw LaTeX code.
+    /// The string is neither formatted nor escaped.
+    pub fn raw(_text: &str) -> Self {
         Self {
-            text: format_text(String::from(_text)),
+            content: vec![Inline::Raw(String::from(_text))],
+            error: None,
+            dedent: Dedent::Common,
             indent: 0
         }
     }
 
-    /// Gets a clone of the `Text`'s string.
+    /// Sets how the indentation of the lines is removed. Defaults to `Dedent::Common`.
+    pub fn set_dedent(&mut self, dedent: Dedent) {
+        self.dedent = dedent;
+    }
+
+    /// Gets the `Text`'s string, formatted as LaTeX, with the indentation of its lines removed by its `Dedent`.
     pub fn get_string(&self) -> String {
-        self.text.clone()
+        let latex = render_inlines(&self.content);
+        let lines: Vec<&str> = latex.lines().collect();
+
+        if lines.len() < 2 {
+            return latex;
+        }
+
+        let mut dedented = self.dedent.apply(&lines).join("\n");
+
+        if latex.ends_with('\n') {
+            dedented.push('\n');
+        }
+
+        dedented
+    }
+
+    pub(crate) fn render(&self) -> Result<String, RustexError> {
+        match &self.error {
+            Some(error) => Err(RustexError::format(&error.to_string())),
+            None => Ok(self.get_string())
+        }
+    }
+
+    /// Renders the `Text` on a single line, for a caption, a table cell or a list label.
+    /// The lines are joined by spaces.
+    pub(crate) fn render_inline(&self) -> Result<String, RustexError> {
+        self.validate_inline()?;
+
+        let inline_str = self.render()?
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<&str>>()
+            .join(" ");
+
+        Ok(inline_str)
+    }
+
+    /// Checks that the `Text` can be written on a single line:
+    /// a paragraph break or a line break would end the caption, the table row or the list label.
+    pub(crate) fn validate_inline(&self) -> Result<(), RustexError> {
+        if inlines_contain_line_break(&self.content) {
+            return Err(RustexError::validation(
+                "a line break cannot be written in a caption, a table cell or a list label"
+            ));
+        }
+
+        let latex = render_inlines(&self.content);
+        let lines: Vec<&str> = latex.lines().map(|line| line.trim()).collect();
+        let first_line = lines.iter().position(|line| !line.is_empty());
+        let last_line = lines.iter().rposition(|line| !line.is_empty());
+
+        if let (Some(first_line), Some(last_line)) = (first_line, last_line) {
+            if lines[first_line..last_line].iter().any(|line| line.is_empty()) {
+                return Err(RustexError::validation(
+                    "a paragraph break cannot be written in a caption, a table cell or a list label"
+                ));
+            }
+        }
+
+        Ok(())
     }
 }
 
 impl Item for Text {
-    fn build(&self, doc: &Document) -> Result<(), Error> {
-        let mut formatted_text = self.text.replace("  ", "");
-        formatted_text = formatted_text.replace("\n", " ");
-        write_indented_line(&doc, &self.indent, formatted_text.as_str())?;
-        doc.add_blank_line()
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        let formatted_text = self.render()?;
+        let mut is_previous_line_blank = true;
+
+        // Consecutive blank lines are written as a single paragraph break
+        for line in formatted_text.lines() {
+            let is_line_blank = line.trim().is_empty();
+
+            match is_line_blank {
+                true if !is_previous_line_blank => doc.add_blank_line()?,
+                true => {}
+                false => write_indented_line(doc, &self.indent, line.trim_end())?
+            }
+
+            is_previous_line_blank = is_line_blank;
+        }
+
+        if !is_previous_line_blank {
+            doc.add_blank_line()?;
+        }
+
+        Ok(())
     }
 
     fn update_indent(&mut self, super_indent: &usize) {
         self.indent = super_indent + 1;
     }
+
+    fn describe(&self) -> String {
+        String::from("Text")
+    }
+
+    fn required_packages(&self) -> Vec<Package> {
+        inlines_required_packages(&self.content)
+    }
+
+    fn check_references(&self, registry: &LabelRegistry) -> Result<(), RustexError> {
+        for label in inlines_references(&self.content) {
+            registry.check_reference(&label)?;
+        }
+
+        Ok(())
+    }
 }
 
 /// An object that can display images, graphics, etc.
 /// Refer to `figure` in LaTeX documentation for more information.
+///
+/// A `Figure` can also be initialized with typed options by `Figure::builder`,
+/// or arrange several `SubFigures` in a grid, with one overall caption.
+///
+/// Example:
+///
+/// ```rust
+/// # use rustex::*;
+/// let mut figure = Figure::from_subfigures("H", true, Some(Text::new("Results")), "fig_results");
+/// figure.set_columns(2);
+///
+/// for (index, plot) in ["a.png", "b.png", "c.png", "d.png"].iter().enumerate() {
+///     let label = format!("fig_results_{}", index + 1);
+///     figure.add_subfigure(SubFigure::new(plot, "", Some(Text::new(plot)), &label));
+/// }
+///
+/// let text = Text::new(&format!("See {}.", Ref::new(&Label::new("fig_results_2"))));
+/// ```
+///
+/// Generated LaTeX:
+///
+/// ```tex
+/// \begin{figure}[H]
+///     \centering
+///     \begin{subfigure}[b]{0.48\textwidth}
+///         \centering
+///         \includegraphics[width=\linewidth]{a.png}
+///         \caption{a.png} \label{fig_results_1}
+///     \end{subfigure}
+///     \hfill
+///     \begin{subfigure}[b]{0.48\textwidth}
+///         ...
+///     \end{subfigure}
+///     \par\medskip
+///     ...
+///     \caption{Results} \label{fig_results}
+/// \end{figure}
+/// ```
 pub struct Figure {
     positioning: String,
     centered: bool,
     image_path: String,
     image_option: String,
+    subfigures: Vec<SubFigure>,
+    columns: Option<usize>,
     caption: Option<Text>,
+    caption_position: CaptionPosition,
     label: String,
+    image_check: Option<ImageCheck>,
     indent: usize
 }
 
@@ -92,63 +446,438 @@ impl Figure {
             centered: _centered,
             image_path: String::from(_image_path),
             image_option: String::from(_image_option),
+            subfigures: Vec::new(),
+            columns: None,
             caption: _caption,
+            caption_position: CaptionPosition::Below,
             label: String::from(_label),
+            image_check: None,
             indent: 0
         }
     }
 
-    fn build_header(&self, doc: &Document) -> Result<(), Error> {
-        let mut begin_figure_str = format!("{}{}", DEF_BEGIN_FIGURE, into_brackets(&self.positioning));
-        begin_figure_str = format!("{} {}", begin_figure_str, into_label(&self.label));
-        write_indented_line(&doc, &self.indent, &begin_figure_str)
+    /// Initializes a new `FigureBuilder`, to set the options of a `Figure` with types
+    pub fn builder(_image_path: &str, _label: &str) -> FigureBuilder {
+        FigureBuilder::new(_image_path, _label)
+    }
+
+    /// Initializes a new `Figure` object made of `SubFigures` only
+    pub fn from_subfigures(_positioning: &str, _centered: bool, _caption: Option<Text>, _label: &str) -> Self {
+        Self::new(_positioning, _centered, "", "", _caption, _label)
+    }
+
+    /// Adds a `SubFigure` to the `Figure`, after its image if it has one
+    pub fn add_subfigure(&mut self, subfigure: SubFigure) {
+        self.subfigures.push(subfigure);
+    }
+
+    /// Sets the number of `SubFigures` per row. Defaults to every `SubFigure` on a single row.
+    /// `SubFigures` without a width share the width of their row.
+    pub fn set_columns(&mut self, columns: usize) {
+        self.columns = Some(columns.max(1));
+    }
+
+    fn get_columns(&self) -> usize {
+        self.columns.unwrap_or(self.subfigures.len()).max(1)
     }
 
-    fn build_caption(&self, doc: &Document, inner_indent: &usize) -> Result<(), Error> {
+    /// Gets the `Label` of the `Figure`, to be referenced in a `Text`.
+    pub fn get_label(&self) -> Label {
+        Label::new(&self.label)
+    }
+
+    fn check_images(&self) -> Result<(), RustexError> {
+        if let Some(image_check) = &self.image_check {
+            if !self.image_path.is_empty() {
+                image_check.check(&self.image_path)?;
+            }
+
+            for subfigure in &self.subfigures {
+                image_check.check(&subfigure.image_path).map_err(|error| error.within(subfigure.describe()))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn build_header(&self, doc: &Document) -> Result<(), RustexError> {
+        let begin_figure_str = match self.positioning.is_empty() {
+            true => String::from(DEF_BEGIN_FIGURE),
+            false => format!("{}{}", DEF_BEGIN_FIGURE, into_brackets(&self.positioning))
+        };
+
+        write_indented_line(doc, &self.indent, &begin_figure_str)
+    }
+
+    // The label follows the caption so that references point to the figure number
+    fn build_caption(&self, doc: &Document, inner_indent: &usize, position: CaptionPosition) -> Result<(), RustexError> {
+        if position != self.caption_position {
+            return Ok(());
+        }
+
         match &self.caption {
             Some(caption) => {
-                let caption_str = format!("{}{}", DEF_CAPTION, into_braces(&caption.get_string()));
-                write_indented_line(&doc, inner_indent, &caption_str)
+                let caption_str = format!("{}{} {}", DEF_CAPTION, into_braces(&caption.render_inline()?),
+                                          into_label(&self.label));
+                write_indented_line(doc, inner_indent, &caption_str)
             }
-            None => Ok(())
+            None => write_indented_line(doc, inner_indent, &into_label(&self.label))
         }
     }
 
-    fn build_centering(&self, doc: &Document, inner_indent: &usize) -> Result<(), Error> {
+    fn build_centering(&self, doc: &Document, inner_indent: &usize) -> Result<(), RustexError> {
         if self.centered {
-            write_indented_line(&doc, inner_indent, DEF_CENTERING)?;
+            write_indented_line(doc, inner_indent, DEF_CENTERING)?;
         }
 
         Ok(())
     }
 
-    fn build_graphic(&self, doc: &Document, inner_indent: &usize) -> Result<(), Error> {
+    fn build_graphic(&self, doc: &Document, inner_indent: &usize) -> Result<(), RustexError> {
+        if self.image_path.is_empty() {
+            return Ok(());
+        }
+
         let include_graph_str = format!("{}{}{}", DEF_INCLUDE_GRAPH,
                                         into_brackets(&self.image_option),
-                                        into_braces(&self.image_path));
+                                        into_braces(&doc.get_asset_path(&self.image_path)));
 
-        write_indented_line(&doc, inner_indent, &include_graph_str)
+        write_indented_line(doc, inner_indent, &include_graph_str)
     }
 
-    fn build_end(&self, doc: &Document) -> Result<(), Error> {
+    // Subfigures of a row are spread with `\hfill`, and rows are separated by a vertical space
+    fn build_subfigures(&self, doc: &Document, inner_indent: &usize) -> Result<(), RustexError> {
+        let columns = self.get_columns();
+
+        for (index, subfigure) in self.subfigures.iter().enumerate() {
+            if index > 0 {
+                match index % columns {
+                    0 => write_indented_line(doc, inner_indent, DEF_SUBFIGURE_ROW_SEPARATOR)?,
+                    _ => write_indented_line(doc, inner_indent, DEF_HORIZONTAL_FILL)?
+                }
+            }
+
+            doc.build_item(subfigure)?;
+        }
+
+        Ok(())
+    }
+
+    fn build_end(&self, doc: &Document) -> Result<(), RustexError> {
         write_indented_line(doc, &self.indent, DEF_END_FIGURE)?;
         doc.add_blank_line()
     }
 }
 
 impl Item for Figure {
-    fn build(&self, doc: &Document) -> Result<(), Error> {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
         let inner_indent: &usize = &(self.indent + 1);
 
+        self.validate()?;
         self.build_header(doc)?;
         self.build_centering(doc, inner_indent)?;
+        self.build_caption(doc, inner_indent, CaptionPosition::Above)?;
         self.build_graphic(doc, inner_indent)?;
-        self.build_caption(doc, inner_indent)?;
+        self.build_subfigures(doc, inner_indent)?;
+        self.build_caption(doc, inner_indent, CaptionPosition::Below)?;
         self.build_end(doc)
     }
 
+    // The default width of the subfigures depends on the number of columns, known once the figure is complete
     fn update_indent(&mut self, super_indent: &usize) {
         self.indent= super_indent + 1;
+
+        let default_width = format!("{:.2}{}", SUBFIGURES_TOTAL_WIDTH / self.get_columns() as f64, DEF_TEXT_WIDTH);
+
+        for subfigure in &mut self.subfigures {
+            subfigure.update_indent(&self.indent);
+            subfigure.default_width = default_width.clone();
+        }
+    }
+
+    fn describe(&self) -> String {
+        format!("Figure '{}'", self.label)
+    }
+
+    fn required_packages(&self) -> Vec<Package> {
+        let mut packages = vec![Package::new(GRAPHICX_PACKAGE, vec![])];
+
+        if self.positioning.contains(FLOAT_HERE_POSITIONING) {
+            packages.push(Package::new(FLOAT_PACKAGE, vec![]));
+        }
+
+        if let Some(caption) = &self.caption {
+            packages.extend(caption.required_packages());
+        }
+
+        for subfigure in &self.subfigures {
+            packages.extend(subfigure.required_packages());
+        }
+
+        packages
+    }
+
+    fn register_labels(&self, registry: &mut LabelRegistry) -> Result<(), RustexError> {
+        registry.register(&self.get_label())?;
+
+        for subfigure in &self.subfigures {
+            subfigure.register_labels(registry).map_err(|error| error.within(subfigure.describe()))?;
+        }
+
+        Ok(())
+    }
+
+    fn check_references(&self, registry: &LabelRegistry) -> Result<(), RustexError> {
+        if let Some(caption) = &self.caption {
+            caption.check_references(registry)?;
+        }
+
+        for subfigure in &self.subfigures {
+            subfigure.check_references(registry).map_err(|error| error.within(subfigure.describe()))?;
+        }
+
+        Ok(())
+    }
+
+    fn validate(&self) -> Result<(), RustexError> {
+        self.check_images()?;
+
+        if let Some(caption) = &self.caption {
+            caption.validate_inline()?;
+        }
+
+        for subfigure in &self.subfigures {
+            subfigure.validate().map_err(|error| error.within(subfigure.describe()))?;
+        }
+
+        Ok(())
+    }
+
+    fn register_assets(&self, assets: &mut AssetManager) -> Result<(), RustexError> {
+        assets.register(&self.image_path)?;
+
+        for subfigure in &self.subfigures {
+            subfigure.register_assets(assets).map_err(|error| error.within(subfigure.describe()))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Sets the options of a `Figure` with types.
+///
+/// Example:
+///
+/// ```rust
+/// # use rustex::*;
+/// # use std::path::Path;
+/// let figure: Figure = Figure::builder("plots/latency.png", "fig_latency")
+///     .placement(&[Placement::Here, Placement::Top, Placement::Force])
+///     .graphics(GraphicsOptions::new().width(Length::TextWidth(0.8)))
+///     .caption(Text::new("Latency per **request**"))
+///     .caption_position(CaptionPosition::Above)
+///     .check_image(Engine::PdfLatex, Path::new("generated_tex"))
+///     .build()
+///     .unwrap();
+/// ```
+///
+/// Generated LaTeX:
+///
+/// ```tex
+/// \begin{figure}[!ht]
+///     \centering
+///     \caption{Latency per \textbf{request}} \label{fig_latency}
+///     \includegraphics[width=0.8\textwidth]{plots/latency.png}
+/// \end{figure}
+/// ```
+///
+/// The image is checked when the `Document` is built, before anything is written.
+pub struct FigureBuilder {
+    image_path: String,
+    label: String,
+    placements: Vec<Placement>,
+    centered: bool,
+    graphics: GraphicsOptions,
+    caption: Option<Text>,
+    caption_position: CaptionPosition,
+    image_check: Option<ImageCheck>
+}
+
+impl FigureBuilder {
+    /// Initializes a new `FigureBuilder` object, for a centered `Figure` placed by LaTeX
+    pub fn new(_image_path: &str, _label: &str) -> Self {
+        Self {
+            image_path: String::from(_image_path),
+            label: String::from(_label),
+            placements: Vec::new(),
+            centered: true,
+            graphics: GraphicsOptions::new(),
+            caption: None,
+            caption_position: CaptionPosition::Below,
+            image_check: None
+        }
+    }
+
+    /// Sets the placements of the `Figure`, e.g. `[Placement::Here, Placement::Top]`. Defaults to the LaTeX placements.
+    pub fn placement(mut self, placements: &[Placement]) -> Self {
+        self.placements = placements.to_vec();
+        self
+    }
+
+    /// Sets whether the content of the `Figure` is centered. Defaults to `true`.
+    pub fn centered(mut self, centered: bool) -> Self {
+        self.centered = centered;
+        self
+    }
+
+    /// Sets the options of the image (`\includegraphics`).
+    pub fn graphics(mut self, graphics: GraphicsOptions) -> Self {
+        self.graphics = graphics;
+        self
+    }
+
+    /// Sets the caption of the `Figure`.
+    pub fn caption(mut self, caption: Text) -> Self {
+        self.caption = Some(caption);
+        self
+    }
+
+    /// Sets whether the caption is above or below the image. Defaults to below.
+    pub fn caption_position(mut self, caption_position: CaptionPosition) -> Self {
+        self.caption_position = caption_position;
+        self
+    }
+
+    /// Checks that the images of the `Figure` exist and have a format supported by the `Engine`.
+    /// Relative image paths are resolved from `base_dir`, the directory of the LaTeX file.
+    pub fn check_image(mut self, engine: Engine, base_dir: &Path) -> Self {
+        self.image_check = Some(ImageCheck::new(engine, base_dir));
+        self
+    }
+
+    /// Builds the `Figure`, or returns an error if its placements cannot be combined.
+    pub fn build(self) -> Result<Figure, RustexError> {
+        let positioning = into_positioning(&self.placements)?;
+        let mut figure = Figure::new(&positioning, self.centered, &self.image_path,
+                                     &self.graphics.to_string(), self.caption, &self.label);
+
+        figure.caption_position = self.caption_position;
+        figure.image_check = self.image_check;
+        Ok(figure)
+    }
+}
+
+/// An image with its own caption and label, arranged with others in a `Figure`.
+/// Refer to `subfigure` in the `subcaption` package documentation for more information.
+/// The `subcaption` package is added to the `Document` automatically.
+pub struct SubFigure {
+    image_path: String,
+    image_option: String,
+    width: Option<String>,
+    default_width: String,
+    caption: Option<Text>,
+    label: String,
+    indent: usize
+}
+
+impl SubFigure {
+    /// Initializes a new `SubFigure` object
+    /// An empty image option scales the image to the width of the `SubFigure`.
+    pub fn new(_image_path: &str, _image_option: &str, _caption: Option<Text>, _label: &str) -> Self {
+        Self {
+            image_path: String::from(_image_path),
+            image_option: String::from(_image_option),
+            width: None,
+            default_width: String::from(DEF_LINE_WIDTH),
+            caption: _caption,
+            label: String::from(_label),
+            indent: 0
+        }
+    }
+
+    /// Sets the width of the `SubFigure`, e.g. `0.3\textwidth` or `5cm`.
+    pub fn set_width(&mut self, width: &str) {
+        self.width = Some(String::from(width));
+    }
+
+    /// Gets the `Label` of the `SubFigure`, to be referenced in a `Text`.
+    pub fn get_label(&self) -> Label {
+        Label::new(&self.label)
+    }
+}
+
+impl Item for SubFigure {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        let inner_indent: &usize = &(self.indent + 1);
+        let width = self.width.as_ref().unwrap_or(&self.default_width);
+
+        let begin_str = format!("{}{}{}", DEF_BEGIN_SUBFIGURE, into_brackets(&String::from(SUBFIGURE_ALIGNMENT)),
+                                into_braces(width));
+        write_indented_line(doc, &self.indent, &begin_str)?;
+        write_indented_line(doc, inner_indent, DEF_CENTERING)?;
+
+        let image_option = match self.image_option.is_empty() {
+            true => format!("width={}", DEF_LINE_WIDTH),
+            false => self.image_option.clone()
+        };
+
+        let include_graph_str = format!("{}{}{}", DEF_INCLUDE_GRAPH, into_brackets(&image_option),
+                                        into_braces(&doc.get_asset_path(&self.image_path)));
+        write_indented_line(doc, inner_indent, &include_graph_str)?;
+
+        match &self.caption {
+            Some(caption) => {
+                let caption_str = format!("{}{} {}", DEF_CAPTION, into_braces(&caption.render_inline()?),
+                                          into_label(&self.label));
+                write_indented_line(doc, inner_indent, &caption_str)?;
+            }
+            None => write_indented_line(doc, inner_indent, &into_label(&self.label))?
+        }
+
+        write_indented_line(doc, &self.indent, DEF_END_SUBFIGURE)
+    }
+
+    fn update_indent(&mut self, super_indent: &usize) {
+        self.indent = super_indent + 1;
+    }
+
+    fn describe(&self) -> String {
+        format!("SubFigure '{}'", self.label)
+    }
+
+    fn required_packages(&self) -> Vec<Package> {
+        let mut packages = vec![
+            Package::new(GRAPHICX_PACKAGE, vec![]),
+            Package::new(SUBCAPTION_PACKAGE, vec![])
+        ];
+
+        if let Some(caption) = &self.caption {
+            packages.extend(caption.required_packages());
+        }
+
+        packages
+    }
+
+    fn register_labels(&self, registry: &mut LabelRegistry) -> Result<(), RustexError> {
+        registry.register(&self.get_label())
+    }
+
+    fn check_references(&self, registry: &LabelRegistry) -> Result<(), RustexError> {
+        match &self.caption {
+            Some(caption) => caption.check_references(registry),
+            None => Ok(())
+        }
+    }
+
+    fn validate(&self) -> Result<(), RustexError> {
+        match &self.caption {
+            Some(caption) => caption.validate_inline(),
+            None => Ok(())
+        }
+    }
+
+    fn register_assets(&self, assets: &mut AssetManager) -> Result<(), RustexError> {
+        assets.register(&self.image_path)
     }
 }
 
@@ -169,13 +898,17 @@ impl Command {
 }
 
 impl Item for Command {
-    fn build(&self, doc: &Document) -> Result<(), Error> {
-        write_indented_line(&doc, &self.indent, &self.command)
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        write_indented_line(doc, &self.indent, &self.command)
     }
 
     fn update_indent(&mut self, super_indent: &usize) {
         self.indent= super_indent + 1;
     }
+
+    fn describe(&self) -> String {
+        format!("Command '{}'", self.command)
+    }
 }
 
 /// An object to add a page jump to a `Document`.
@@ -192,9 +925,15 @@ impl PageBreak {
     }
 }
 
+impl Default for PageBreak {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Item for PageBreak {
-    fn build(&self, doc: &Document) -> Result<(), Error> {
-        write_indented_line(&doc, &self.indent, DEF_NEW_PAGE)?;
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        write_indented_line(doc, &self.indent, DEF_NEW_PAGE)?;
         doc.add_blank_line()
     }
 
@@ -202,4 +941,99 @@ impl Item for PageBreak {
         self.indent= super_indent + 1;
     }
 
-}
\ No newline at end of file
+    fn describe(&self) -> String {
+        String::from("PageBreak")
+    }
+
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dedents_the_string() {
+        let text = Text::new("First line,
+            second line
+                indented line");
+        assert_eq!(text.get_string(), "First line,\nsecond line\n    indented line");
+
+        let mut text = Text::new("  a\n    b");
+        text.set_dedent(Dedent::All);
+        assert_eq!(text.get_string(), "a\nb");
+
+        text.set_dedent(Dedent::None);
+        assert_eq!(text.get_string(), "  a\n    b");
+    }
+
+    #[test]
+    fn dedents_only_shared_spaces_and_tabs() {
+        let text = Text::new("First line\n  a\n\u{3000}b");
+        assert_eq!(text.get_string(), "First line\n  a\n\u{3000}b");
+
+        let text = Text::new("First line\n  \u{3000}a\n  b");
+        assert_eq!(text.get_string(), "First line\n\u{3000}a\nb");
+
+        let text = Text::new("First line\n\t  a\n\t\tb\n    c");
+        assert_eq!(text.get_string(), "First line\n\t  a\n\t\tb\n    c");
+
+        let text = Text::new("First line\n\t  a\n\t\tb");
+        assert_eq!(text.get_string(), "First line\n  a\n\tb");
+    }
+
+    #[test]
+    fn renders_multi_line_text_inline() {
+        let caption = Text::new("A **long**
+            caption
+        ");
+        assert_eq!(caption.render_inline().unwrap(), r"A \textbf{long} caption");
+
+        let caption = Text::new("\n  Surrounded by blank lines\n\n");
+        assert_eq!(caption.render_inline().unwrap(), "Surrounded by blank lines");
+    }
+
+    #[test]
+    fn rejects_breaks_in_inline_text() {
+        let paragraphs = Text::new("First paragraph\n\n    Second paragraph");
+        assert_eq!(paragraphs.render_inline().unwrap_err().to_string(),
+                   "validation error: a paragraph break cannot be written in a caption, a table cell or a list label");
+
+        let line_break = Text::new("First line\\\\\n    Second line");
+        assert_eq!(line_break.render_inline().unwrap_err().to_string(),
+                   "validation error: a line break cannot be written in a caption, a table cell or a list label");
+    }
+
+    #[test]
+    fn drops_line_breaks_before_paragraph_breaks() {
+        let text = Text::new("First paragraph  \n\n    Second paragraph\\\\\n");
+        assert_eq!(text.get_string(), "First paragraph\n\nSecond paragraph\n");
+    }
+
+    #[test]
+    fn checks_images_when_validating() {
+        let figure = Figure::builder("rustex_missing_plot.png", "fig_missing")
+            .check_image(Engine::PdfLatex, &std::env::temp_dir())
+            .build()
+            .unwrap();
+        assert!(Item::validate(&figure).is_err());
+
+        let figure = Figure::builder("rustex_missing_plot.png", "fig_missing").build().unwrap();
+        assert!(Item::validate(&figure).is_ok());
+    }
+
+    #[test]
+    fn describes_items_by_their_type_name() {
+        struct Chart<T>(T);
+
+        impl<T> Item for Chart<T> {
+            fn build(&self, _doc: &Document) -> Result<(), RustexError> {
+                Ok(())
+            }
+
+            fn update_indent(&mut self, _super_indent: &usize) {}
+        }
+
+        assert_eq!(Chart(Text::new("")).describe(), "Chart<Text>");
+        assert_eq!(type_short_name("alloc::vec::Vec<(u8, core::option::Option<&str>)>"), "Vec<(u8, Option<&str>)>");
+    }
+}
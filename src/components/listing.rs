@@ -0,0 +1,449 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use super::item::*;
+use super::document::*;
+use super::package::*;
+use super::reference::*;
+use crate::error::RustexError;
+use crate::utilities::def_syntax::*;
+use crate::utilities::format::*;
+
+// Languages predefined by `listings`, other languages are written without highlighting
+const LISTINGS_LANGUAGES: [&str; 33] = [
+    "ada", "assembler", "awk", "bash", "c", "c++", "caml", "cobol", "csh", "delphi", "erlang",
+    "fortran", "haskell", "html", "java", "ksh", "lisp", "lua", "make", "matlab", "ocaml", "octave",
+    "pascal", "perl", "php", "python", "r", "ruby", "sh", "sql", "tcl", "tex", "xml"
+];
+
+/// Defines the LaTeX package used to typeset a `CodeListing`.
+pub enum ListingBackend {
+    /// `listings`, which works with any engine.
+    Listings,
+    /// `minted`, which highlights more languages but needs `-shell-escape` and Python's `pygments`.
+    Minted
+}
+
+/// An object to display source code.
+/// The code is written verbatim, so whitespace is preserved and no markup is applied.
+/// The code and the end of its environment are not indented, as they would be part of the listing.
+///
+/// The `listings` backend is used by default, and only highlights the languages it predefines
+/// (SQL, Bash, Python, C, etc.). The `minted` backend highlights every language of `pygments`, such as Rust,
+/// but the `Compiler` must then be run with `set_shell_escape(true)`.
+/// Code without a language is written without highlighting, as `text` with the `minted` backend.
+///
+/// Example:
+///
+/// ```rust
+/// # use rustex::*;
+/// let mut listing = CodeListing::new(
+///     "SELECT name\nFROM users\nWHERE age > 18;", // Code
+///     "sql",                                       // Language
+///     "lst_query"                                  // Label
+/// );
+///
+/// listing.set_line_numbers(true);
+/// listing.set_caption(Text::new("Selecting the **adult** users"));
+/// listing.add_highlighted_lines(3, 3);
+/// ```
+///
+/// Generated LaTeX:
+///
+/// ```tex
+/// \begin{lstlisting}[basicstyle=\ttfamily, language=sql, numbers=left, escapeinside={(*@}{@*)}, caption={Selecting the \textbf{adult} users}, label={lst_query}]
+/// SELECT name
+/// FROM users
+/// (*@\makebox[0pt][l]{\color{yellow!30}\rule[-0.3\baselineskip]{\linewidth}{\baselineskip}}@*)WHERE age > 18;
+/// \end{lstlisting}
+/// ```
+pub struct CodeListing {
+    code: String,
+    language: String,
+    label: String,
+    backend: ListingBackend,
+    line_numbers: bool,
+    caption: Option<Text>,
+    highlighted_lines: Vec<(usize, usize)>,
+    highlight_color: String,
+    indent: usize
+}
+
+impl CodeListing {
+    /// Initializes a new `CodeListing` object
+    /// An empty label adds no `\label` to the `CodeListing`.
+    pub fn new(_code: &str, _language: &str, _label: &str) -> Self {
+        Self {
+            code: String::from(_code.trim_end_matches(['\n', '\r'])),
+            language: String::from(_language),
+            label: String::from(_label),
+            backend: ListingBackend::Listings,
+            line_numbers: false,
+            caption: None,
+            highlighted_lines: Vec::new(),
+            highlight_color: String::from(DEFAULT_HIGHLIGHT_COLOR),
+            indent: 0
+        }
+    }
+
+    /// Initializes a new `CodeListing` object with the content of a source file.
+    pub fn from_file(_path: &Path, _language: &str, _label: &str) -> Result<Self, RustexError> {
+        let code = fs::read_to_string(_path)?;
+        Ok(Self::new(&code, _language, _label))
+    }
+
+    pub fn set_backend(&mut self, backend: ListingBackend) {
+        self.backend = backend;
+    }
+
+    pub fn set_line_numbers(&mut self, line_numbers: bool) {
+        self.line_numbers = line_numbers;
+    }
+
+    pub fn set_caption(&mut self, caption: Text) {
+        self.caption = Some(caption);
+    }
+
+    /// Sets the background color of the highlighted lines, e.g. `yellow!30`.
+    pub fn set_highlight_color(&mut self, color: &str) {
+        self.highlight_color = String::from(color);
+    }
+
+    /// Highlights the lines from `start` to `end` (both included, starting at 1).
+    pub fn add_highlighted_lines(&mut self, start: usize, end: usize) {
+        self.highlighted_lines.push((start, end));
+    }
+
+    /// Gets the `Label` of the `CodeListing`, to be referenced in a `Text`.
+    pub fn get_label(&self) -> Label {
+        Label::new(&self.label)
+    }
+
+    fn is_highlighted(&self, line_number: usize) -> bool {
+        self.highlighted_lines
+            .iter()
+            .any(|(start, end)| *start <= line_number && line_number <= *end)
+    }
+
+    fn get_end(&self) -> &str {
+        match self.backend {
+            ListingBackend::Listings => DEF_END_LSTLISTING,
+            ListingBackend::Minted => DEF_END_MINTED
+        }
+    }
+
+    fn build_listings(&self, doc: &Document) -> Result<(), RustexError> {
+        let mut options: Vec<String> = vec![String::from(LISTINGS_BASIC_STYLE)];
+
+        if LISTINGS_LANGUAGES.contains(&self.language.to_lowercase().as_str()) {
+            options.push(format!("language={}", self.language));
+        }
+
+        if self.line_numbers {
+            options.push(String::from(LISTINGS_LINE_NUMBERS));
+        }
+
+        if !self.highlighted_lines.is_empty() {
+            options.push(format!("escapeinside={}{}", into_braces(&String::from(LISTINGS_ESCAPE_BEGIN)),
+                                 into_braces(&String::from(LISTINGS_ESCAPE_END))));
+        }
+
+        if let Some(caption) = &self.caption {
+            options.push(format!("caption={}", into_braces(&caption.render_inline()?)));
+        }
+
+        if !self.label.is_empty() {
+            options.push(format!("label={}", into_braces(&self.label)));
+        }
+
+        let begin_str = format!("{}{}", DEF_BEGIN_LSTLISTING, into_brackets(&options.join(", ")));
+        write_indented_line(doc, &self.indent, &begin_str)?;
+
+        // A highlighted line starts with a colored box behind the code, escaped from the listing
+        let highlight_str = format!("{}{}{}{}", LISTINGS_ESCAPE_BEGIN, DEF_HIGHLIGHT_BOX,
+                                    into_braces(&format!("{}{}{}", DEF_COLOR, into_braces(&self.highlight_color),
+                                                         DEF_HIGHLIGHT_RULE)),
+                                    LISTINGS_ESCAPE_END);
+
+        for (index, line) in self.code.lines().enumerate() {
+            match self.is_highlighted(index + 1) {
+                true => writeln!(doc.get_writer(), "{}{}", highlight_str, line)?,
+                false => writeln!(doc.get_writer(), "{}", line)?
+            }
+        }
+
+        writeln!(doc.get_writer(), "{}", DEF_END_LSTLISTING)?;
+        Ok(())
+    }
+
+    // `minted` has no caption, so captioned or labeled code is wrapped in a `listing` float
+    fn build_minted(&self, doc: &Document) -> Result<(), RustexError> {
+        let is_float = self.caption.is_some() || !self.label.is_empty();
+        let inner_indent = match is_float {
+            true => self.indent + 1,
+            false => self.indent
+        };
+
+        if is_float {
+            write_indented_line(doc, &self.indent, DEF_BEGIN_LISTING)?;
+        }
+
+        let mut options: Vec<String> = Vec::new();
+
+        if self.line_numbers {
+            options.push(String::from(MINTED_LINE_NUMBERS));
+        }
+
+        if !self.highlighted_lines.is_empty() {
+            let ranges: Vec<String> = self.highlighted_lines
+                .iter()
+                .map(|(start, end)| format!("{}-{}", start, end))
+                .collect();
+
+            options.push(format!("highlightlines={}", into_braces(&ranges.join(","))));
+            options.push(format!("highlightcolor={}", self.highlight_color));
+        }
+
+        let mut begin_str = String::from(DEF_BEGIN_MINTED);
+
+        if !options.is_empty() {
+            begin_str = format!("{}{}", begin_str, into_brackets(&options.join(", ")));
+        }
+
+        // `minted` needs a lexer, `text` writes the code without highlighting
+        let language = match self.language.trim() {
+            "" => String::from(MINTED_PLAIN_LANGUAGE),
+            language => language.to_lowercase()
+        };
+
+        begin_str = format!("{}{}", begin_str, into_braces(&language));
+        write_indented_line(doc, &inner_indent, &begin_str)?;
+
+        for line in self.code.lines() {
+            writeln!(doc.get_writer(), "{}", line)?;
+        }
+
+        writeln!(doc.get_writer(), "{}", DEF_END_MINTED)?;
+
+        if is_float {
+            match &self.caption {
+                Some(caption) => {
+                    let caption_str = format!("{}{} {}", DEF_CAPTION, into_braces(&caption.render_inline()?),
+                                              into_label(&self.label));
+                    write_indented_line(doc, &inner_indent, &caption_str)?;
+                }
+                None => write_indented_line(doc, &inner_indent, &into_label(&self.label))?
+            }
+
+            write_indented_line(doc, &self.indent, DEF_END_LISTING)?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Item for CodeListing {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        self.validate()?;
+
+        match self.backend {
+            ListingBackend::Listings => self.build_listings(doc)?,
+            ListingBackend::Minted => self.build_minted(doc)?
+        }
+
+        doc.add_blank_line()
+    }
+
+    fn update_indent(&mut self, super_indent: &usize) {
+        self.indent = super_indent + 1;
+    }
+
+    fn describe(&self) -> String {
+        match self.label.is_empty() {
+            true => String::from("CodeListing"),
+            false => format!("CodeListing '{}'", self.label)
+        }
+    }
+
+    fn validate(&self) -> Result<(), RustexError> {
+        if let Some(caption) = &self.caption {
+            caption.validate_inline()?;
+        }
+
+        let line_count = self.code.lines().count();
+
+        for (start, end) in &self.highlighted_lines {
+            if *start == 0 || start > end || *end > line_count {
+                return Err(RustexError::validation(&format!(
+                    "highlighted lines {}-{} are outside of the listing ({} lines)", start, end, line_count
+                )));
+            }
+        }
+
+        if self.code.contains(self.get_end()) {
+            return Err(RustexError::validation(&format!("the code cannot contain '{}'", self.get_end())));
+        }
+
+        let uses_escapes = matches!(self.backend, ListingBackend::Listings) && !self.highlighted_lines.is_empty();
+
+        if uses_escapes && (self.code.contains(LISTINGS_ESCAPE_BEGIN) || self.code.contains(LISTINGS_ESCAPE_END)) {
+            return Err(RustexError::validation(&format!(
+                "highlighted code cannot contain '{}' or '{}'", LISTINGS_ESCAPE_BEGIN, LISTINGS_ESCAPE_END
+            )));
+        }
+
+        Ok(())
+    }
+
+    fn required_packages(&self) -> Vec<Package> {
+        let mut packages = match self.backend {
+            ListingBackend::Listings => vec![Package::new(LISTINGS_PACKAGE, vec![])],
+            ListingBackend::Minted => vec![Package::new(MINTED_PACKAGE, vec![])]
+        };
+
+        if !self.highlighted_lines.is_empty() {
+            packages.push(Package::new(XCOLOR_PACKAGE, vec![]));
+        }
+
+        if let Some(caption) = &self.caption {
+            packages.extend(caption.required_packages());
+        }
+
+        packages
+    }
+
+    fn register_labels(&self, registry: &mut LabelRegistry) -> Result<(), RustexError> {
+        registry.register(&self.get_label())
+    }
+
+    fn check_references(&self, registry: &LabelRegistry) -> Result<(), RustexError> {
+        match &self.caption {
+            Some(caption) => caption.check_references(registry),
+            None => Ok(())
+        }
+    }
+}
+
+/// An object to display preformatted text, such as log excerpts, in a monospace font.
+/// The text is written verbatim, so whitespace and line breaks are preserved and no markup is applied.
+/// Refer to `verbatim` in LaTeX documentation for more information.
+///
+/// Example:
+///
+/// ```rust
+/// # use rustex::*;
+/// let log = Verbatim::new("[INFO]  server started\n[WARN]  cache_size is **low**");
+/// ```
+///
+/// Generated LaTeX:
+///
+/// ```tex
+/// \begin{verbatim}
+/// [INFO]  server started
+/// [WARN]  cache_size is **low**
+/// \end{verbatim}
+/// ```
+pub struct Verbatim {
+    text: String,
+    indent: usize
+}
+
+impl Verbatim {
+    /// Initializes a new `Verbatim` object
+    pub fn new(_text: &str) -> Self {
+        Self {
+            text: String::from(_text.trim_end_matches(['\n', '\r'])),
+            indent: 0
+        }
+    }
+}
+
+impl Item for Verbatim {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        self.validate()?;
+        write_indented_line(doc, &self.indent, DEF_BEGIN_VERBATIM)?;
+
+        for line in self.text.lines() {
+            writeln!(doc.get_writer(), "{}", line)?;
+        }
+
+        writeln!(doc.get_writer(), "{}", DEF_END_VERBATIM)?;
+        doc.add_blank_line()
+    }
+
+    fn update_indent(&mut self, super_indent: &usize) {
+        self.indent = super_indent + 1;
+    }
+
+    fn describe(&self) -> String {
+        String::from("Verbatim")
+    }
+
+    fn validate(&self) -> Result<(), RustexError> {
+        match self.text.contains(DEF_END_VERBATIM) {
+            true => Err(RustexError::validation(&format!("the text cannot contain '{}'", DEF_END_VERBATIM))),
+            false => Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(listing: CodeListing) -> String {
+        let mut doc = Document::from_writer(Vec::new(), DocumentClass::new(ClassType::Article, vec![]));
+        doc.add_item(listing);
+        doc.render_to_string().unwrap()
+    }
+
+    #[test]
+    fn writes_code_without_language_as_plain_text_with_minted() {
+        let mut listing = CodeListing::new("plain output", "", "");
+        listing.set_backend(ListingBackend::Minted);
+
+        let latex = render(listing);
+        assert!(latex.contains("\\begin{minted}{text}\nplain output\n\\end{minted}"), "{}", latex);
+    }
+
+    #[test]
+    fn writes_the_language_in_lower_case_with_minted() {
+        let mut listing = CodeListing::new("fn main() {}", "Rust", "");
+        listing.set_backend(ListingBackend::Minted);
+
+        assert!(render(listing).contains(r"\begin{minted}{rust}"));
+    }
+
+    #[test]
+    fn writes_code_without_language_without_highlighting_with_listings() {
+        let latex = render(CodeListing::new("plain output", "", ""));
+        assert!(latex.contains(r"\begin{lstlisting}[basicstyle=\ttfamily]"), "{}", latex);
+    }
+
+    #[test]
+    fn validates_listings_before_building() {
+        let mut listing = CodeListing::new("SELECT name\nFROM users;", "sql", "lst_query");
+        listing.add_highlighted_lines(2, 3);
+
+        assert_eq!(Item::validate(&listing).unwrap_err().to_string(),
+                   "validation error: highlighted lines 2-3 are outside of the listing (2 lines)");
+
+        let mut doc = Document::from_writer(Vec::new(), DocumentClass::new(ClassType::Article, vec![]));
+        doc.add_item(Text::new("Before the listing"));
+        doc.add_item(CodeListing::new(r"\end{lstlisting}", "", "lst_end"));
+
+        assert_eq!(doc.render_to_string().unwrap_err().to_string(),
+                   r"CodeListing 'lst_end': validation error: the code cannot contain '\end{lstlisting}'");
+    }
+
+    #[test]
+    fn validates_verbatim_before_building() {
+        let mut doc = Document::from_writer(Vec::new(), DocumentClass::new(ClassType::Article, vec![]));
+        doc.add_item(Text::new("Before the log"));
+        doc.add_item(Verbatim::new("[INFO] \\end{verbatim}"));
+
+        assert_eq!(doc.render_to_string().unwrap_err().to_string(),
+                   r"Verbatim: validation error: the text cannot contain '\end{verbatim}'");
+        assert!(Item::validate(&Verbatim::new("[INFO] server started")).is_ok());
+    }
+}
@@ -0,0 +1,426 @@
+use super::item::*;
+use super::document::*;
+use super::expression::*;
+use super::package::*;
+use super::reference::*;
+use crate::error::RustexError;
+use crate::utilities::def_syntax::*;
+use crate::utilities::format::*;
+
+/// A line of an `Align` or a `Gather`, with an optional `Label`.
+struct MathLine {
+    expression: String,
+    label: String
+}
+
+fn amsmath_package() -> Vec<Package> {
+    vec![Package::new(AMSMATH_PACKAGE, vec![])]
+}
+
+// Unnumbered equations have no number to reference
+fn register_math_label(label: &str, numbered: bool, registry: &mut LabelRegistry) -> Result<(), RustexError> {
+    if !label.is_empty() && !numbered {
+        return Err(RustexError::validation(&format!("label '{}' is set on an unnumbered equation", label)));
+    }
+
+    registry.register(&Label::new(label))
+}
+
+fn with_label(expression: &str, label: &str) -> String {
+    match label.is_empty() {
+        true => String::from(expression),
+        false => format!("{} {}", expression, into_label(&String::from(label)))
+    }
+}
+
+fn build_expression(doc: &Document, indent: &usize, expression: &str) -> Result<(), RustexError> {
+    for line in expression.lines().map(|line| line.trim()).filter(|line| !line.is_empty()) {
+        write_indented_line(doc, indent, line)?;
+    }
+
+    Ok(())
+}
+
+// Every line but the last one ends with `\\`.
+// A multi-line expression is written like the expression of an `Equation`, without blank lines
+fn build_math_lines(doc: &Document, lines: &[MathLine], indent: &usize) -> Result<(), RustexError> {
+    for (index, line) in lines.iter().enumerate() {
+        let mut line_str = with_label(line.expression.trim(), &line.label);
+
+        if index + 1 < lines.len() {
+            line_str = format!("{} {}", line_str, DEF_LINE_BREAK);
+        }
+
+        build_expression(doc, indent, &line_str)?;
+    }
+
+    Ok(())
+}
+
+// Escaped braces (`\{`) and comments are ignored
+fn check_braces(expression: &str) -> Result<(), RustexError> {
+    let mut depth: usize = 0;
+    let mut characters = expression.chars();
+
+    while let Some(character) = characters.next() {
+        match character {
+            '\\' => {
+                characters.next();
+            }
+            '%' => {
+                characters.by_ref().find(|character| *character == '\n');
+            }
+            '{' => depth += 1,
+            '}' if depth == 0 => return Err(RustexError::validation(&format!("unbalanced '}}' in '{}'", expression.trim()))),
+            '}' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    match depth {
+        0 => Ok(()),
+        _ => Err(RustexError::validation(&format!("unclosed '{{' in '{}'", expression.trim())))
+    }
+}
+
+fn validate_math_lines(lines: &[MathLine]) -> Result<(), RustexError> {
+    for line in lines {
+        check_braces(&line.expression)?;
+    }
+
+    Ok(())
+}
+
+fn register_math_lines_labels(lines: &[MathLine], numbered: bool, registry: &mut LabelRegistry) -> Result<(), RustexError> {
+    for line in lines {
+        register_math_label(&line.label, numbered, registry)?;
+    }
+
+    Ok(())
+}
+
+/// A displayed equation.
+/// Refer to `equation` in LaTeX documentation for more information.
+///
+/// The expression is written as is, so it can contain any LaTeX math code.
+/// The `amsmath` package is added to the `Document` automatically.
+///
+/// Example:
+///
+/// ```rust
+/// # use rustex::*;
+/// let equation = Equation::new(r"E_k = \frac{1}{2} m v^2", true, "eq_energy");
+/// let text = Text::new(&format!("The kinetic energy is given by {}.", EqRef::new(&equation.get_label())));
+/// ```
+///
+/// Generated LaTeX:
+///
+/// ```tex
+/// \begin{equation}
+///     E_k = \frac{1}{2} m v^2 \label{eq_energy}
+/// \end{equation}
+/// ```
+pub struct Equation {
+    expression: String,
+    numbered: bool,
+    label: String,
+    indent: usize
+}
+
+impl Equation {
+    /// Initializes a new `Equation` object
+    /// An empty label adds no `\label` to the `Equation`.
+    pub fn new(_expression: &str, _numbered: bool, _label: &str) -> Self {
+        Self {
+            expression: String::from(_expression),
+            numbered: _numbered,
+            label: String::from(_label),
+            indent: 0
+        }
+    }
+
+    /// Initializes a new `Equation` object from a `MathExpr`
+    pub fn from_expression(_expression: &MathExpr, _numbered: bool, _label: &str) -> Self {
+        Self::new(&_expression.to_latex(), _numbered, _label)
+    }
+
+    /// Gets the `Label` of the `Equation`, to be referenced in a `Text`.
+    pub fn get_label(&self) -> Label {
+        Label::new(&self.label)
+    }
+
+    fn get_environment(&self) -> (&str, &str) {
+        match self.numbered {
+            true => (DEF_BEGIN_EQUATION, DEF_END_EQUATION),
+            false => (DEF_BEGIN_UNNUMBERED_EQUATION, DEF_END_UNNUMBERED_EQUATION)
+        }
+    }
+}
+
+impl Item for Equation {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        let (begin_str, end_str) = self.get_environment();
+        let inner_indent: &usize = &(self.indent + 1);
+
+        write_indented_line(doc, &self.indent, begin_str)?;
+        build_expression(doc, inner_indent, &with_label(self.expression.trim(), &self.label))?;
+        write_indented_line(doc, &self.indent, end_str)?;
+        doc.add_blank_line()
+    }
+
+    fn update_indent(&mut self, super_indent: &usize) {
+        self.indent = super_indent + 1;
+    }
+
+    fn describe(&self) -> String {
+        match self.label.is_empty() {
+            true => String::from("Equation"),
+            false => format!("Equation '{}'", self.label)
+        }
+    }
+
+    fn required_packages(&self) -> Vec<Package> {
+        amsmath_package()
+    }
+
+    fn register_labels(&self, registry: &mut LabelRegistry) -> Result<(), RustexError> {
+        register_math_label(&self.label, self.numbered, registry)
+    }
+
+    fn validate(&self) -> Result<(), RustexError> {
+        check_braces(&self.expression)
+    }
+}
+
+/// Equations aligned on their `&` characters, one number per line.
+/// Refer to `align` in LaTeX documentation for more information.
+///
+/// The `amsmath` package is added to the `Document` automatically.
+///
+/// Example:
+///
+/// ```rust
+/// # use rustex::*;
+/// use rustex::math::*;
+///
+/// let mut align = Align::new(true);
+/// align.add_labeled_line(r"f(x) &= (x + 1)^2", "eq_square");
+/// align.add_line(r"&= x^2 + 2x + 1");
+///
+/// // Relations of a `MathExpr` are aligned automatically
+/// align.add_expression(&equals(func("g", "x"), sup("x", 3)));
+/// ```
+///
+/// Generated LaTeX:
+///
+/// ```tex
+/// \begin{align}
+///     f(x) &= (x + 1)^2 \label{eq_square} \\
+///     &= x^2 + 2x + 1 \\
+///     g\left(x\right) &= x^{3}
+/// \end{align}
+/// ```
+pub struct Align {
+    lines: Vec<MathLine>,
+    numbered: bool,
+    indent: usize
+}
+
+impl Align {
+    /// Initializes a new `Align` object
+    pub fn new(_numbered: bool) -> Self {
+        Self {
+            lines: Vec::new(),
+            numbered: _numbered,
+            indent: 0
+        }
+    }
+
+    /// Adds a line to the `Align`
+    pub fn add_line(&mut self, expression: &str) {
+        self.add_labeled_line(expression, "");
+    }
+
+    /// Adds a line with a `Label` to the `Align`
+    pub fn add_labeled_line(&mut self, expression: &str, label: &str) {
+        self.lines.push(MathLine { expression: String::from(expression), label: String::from(label) });
+    }
+
+    /// Adds a `MathExpr` line to the `Align`
+    pub fn add_expression(&mut self, expression: &MathExpr) {
+        self.add_labeled_line(&expression.to_aligned_latex(), "");
+    }
+
+    /// Adds a `MathExpr` line with a `Label` to the `Align`
+    pub fn add_labeled_expression(&mut self, expression: &MathExpr, label: &str) {
+        self.add_labeled_line(&expression.to_aligned_latex(), label);
+    }
+
+    fn get_environment(&self) -> (&str, &str) {
+        match self.numbered {
+            true => (DEF_BEGIN_ALIGN, DEF_END_ALIGN),
+            false => (DEF_BEGIN_UNNUMBERED_ALIGN, DEF_END_UNNUMBERED_ALIGN)
+        }
+    }
+}
+
+impl Item for Align {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        let (begin_str, end_str) = self.get_environment();
+
+        write_indented_line(doc, &self.indent, begin_str)?;
+        build_math_lines(doc, &self.lines, &(self.indent + 1))?;
+        write_indented_line(doc, &self.indent, end_str)?;
+        doc.add_blank_line()
+    }
+
+    fn update_indent(&mut self, super_indent: &usize) {
+        self.indent = super_indent + 1;
+    }
+
+    fn describe(&self) -> String {
+        String::from("Align")
+    }
+
+    fn required_packages(&self) -> Vec<Package> {
+        amsmath_package()
+    }
+
+    fn register_labels(&self, registry: &mut LabelRegistry) -> Result<(), RustexError> {
+        register_math_lines_labels(&self.lines, self.numbered, registry)
+    }
+
+    fn validate(&self) -> Result<(), RustexError> {
+        validate_math_lines(&self.lines)
+    }
+}
+
+/// Centered equations without alignment, one number per line.
+/// Refer to `gather` in LaTeX documentation for more information.
+///
+/// The `amsmath` package is added to the `Document` automatically.
+///
+/// Example:
+///
+/// ```rust
+/// # use rustex::*;
+/// let mut gather = Gather::new(false);
+/// gather.add_line(r"a^2 + b^2 = c^2");
+/// gather.add_line(r"e^{i \pi} + 1 = 0");
+/// ```
+///
+/// Generated LaTeX:
+///
+/// ```tex
+/// \begin{gather*}
+///     a^2 + b^2 = c^2 \\
+///     e^{i \pi} + 1 = 0
+/// \end{gather*}
+/// ```
+pub struct Gather {
+    lines: Vec<MathLine>,
+    numbered: bool,
+    indent: usize
+}
+
+impl Gather {
+    /// Initializes a new `Gather` object
+    pub fn new(_numbered: bool) -> Self {
+        Self {
+            lines: Vec::new(),
+            numbered: _numbered,
+            indent: 0
+        }
+    }
+
+    /// Adds a line to the `Gather`
+    pub fn add_line(&mut self, expression: &str) {
+        self.add_labeled_line(expression, "");
+    }
+
+    /// Adds a line with a `Label` to the `Gather`
+    pub fn add_labeled_line(&mut self, expression: &str, label: &str) {
+        self.lines.push(MathLine { expression: String::from(expression), label: String::from(label) });
+    }
+
+    /// Adds a `MathExpr` line to the `Gather`
+    pub fn add_expression(&mut self, expression: &MathExpr) {
+        self.add_labeled_line(&expression.to_latex(), "");
+    }
+
+    /// Adds a `MathExpr` line with a `Label` to the `Gather`
+    pub fn add_labeled_expression(&mut self, expression: &MathExpr, label: &str) {
+        self.add_labeled_line(&expression.to_latex(), label);
+    }
+
+    fn get_environment(&self) -> (&str, &str) {
+        match self.numbered {
+            true => (DEF_BEGIN_GATHER, DEF_END_GATHER),
+            false => (DEF_BEGIN_UNNUMBERED_GATHER, DEF_END_UNNUMBERED_GATHER)
+        }
+    }
+}
+
+impl Item for Gather {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        let (begin_str, end_str) = self.get_environment();
+
+        write_indented_line(doc, &self.indent, begin_str)?;
+        build_math_lines(doc, &self.lines, &(self.indent + 1))?;
+        write_indented_line(doc, &self.indent, end_str)?;
+        doc.add_blank_line()
+    }
+
+    fn update_indent(&mut self, super_indent: &usize) {
+        self.indent = super_indent + 1;
+    }
+
+    fn describe(&self) -> String {
+        String::from("Gather")
+    }
+
+    fn required_packages(&self) -> Vec<Package> {
+        amsmath_package()
+    }
+
+    fn register_labels(&self, registry: &mut LabelRegistry) -> Result<(), RustexError> {
+        register_math_lines_labels(&self.lines, self.numbered, registry)
+    }
+
+    fn validate(&self) -> Result<(), RustexError> {
+        validate_math_lines(&self.lines)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render<I: Item + 'static>(item: I) -> Result<String, RustexError> {
+        let mut doc = Document::from_writer(Vec::new(), DocumentClass::new(ClassType::Article, vec![]));
+        doc.add_item(item);
+        doc.render_to_string()
+    }
+
+    #[test]
+    fn writes_multi_line_expressions_without_blank_lines() {
+        let mut align = Align::new(false);
+        align.add_line("f(x) &= a\n\n        + b");
+        align.add_labeled_line("g(x) &= c", "");
+
+        let latex = render(align).unwrap();
+        assert!(latex.contains("\\begin{align*}\n\t\tf(x) &= a\n\t\t+ b \\\\\n\t\tg(x) &= c\n\t\\end{align*}"), "{}", latex);
+    }
+
+    #[test]
+    fn rejects_unbalanced_braces() {
+        assert!(check_braces(r"\frac{a}{b} + \{x\} % a comment with {").is_ok());
+        assert!(check_braces(r"x \\{y}").is_ok());
+        assert_eq!(check_braces(r"\frac{a}{b").unwrap_err().to_string(), r"validation error: unclosed '{' in '\frac{a}{b'");
+        assert_eq!(check_braces("a}").unwrap_err().to_string(), "validation error: unbalanced '}' in 'a}'");
+
+        let mut gather = Gather::new(false);
+        gather.add_line("a^{2");
+        assert_eq!(render(gather).unwrap_err().to_string(), "Gather: validation error: unclosed '{' in 'a^{2'");
+    }
+}
@@ -0,0 +1,244 @@
+use std::io::Write;
+use super::document::*;
+use super::item::*;
+use super::package::*;
+use super::reference::*;
+use crate::error::RustexError;
+use crate::utilities::def_syntax::*;
+use crate::utilities::format::*;
+
+/// An author of a `Document`, written on its title page.
+/// An empty affiliation or email is not written.
+pub struct Author {
+    name: String,
+    affiliation: String,
+    email: String
+}
+
+impl Author {
+    /// Initializes a new `Author` object
+    pub fn new(_name: &str, _affiliation: &str, _email: &str) -> Self {
+        Self {
+            name: String::from(_name),
+            affiliation: String::from(_affiliation),
+            email: String::from(_email)
+        }
+    }
+
+    // The name, affiliation and email are written on separate lines
+    fn to_latex(&self) -> String {
+        let mut lines = vec![escape_text(&self.name)];
+
+        if !self.affiliation.is_empty() {
+            lines.push(escape_text(&self.affiliation));
+        }
+
+        if !self.email.is_empty() {
+            lines.push(into_monospace(&escape_text(&self.email)));
+        }
+
+        lines.join(&format!(" {} ", DEF_LINE_BREAK))
+    }
+}
+
+/// Defines the date written on the title page of a `Document`.
+pub enum DocumentDate {
+    /// The date of the compilation (`\today`).
+    Today,
+    /// A date written as text, e.g. `2024 / 05 / 31`, with its LaTeX special characters escaped.
+    Custom(String),
+    /// No date.
+    Empty
+}
+
+impl DocumentDate {
+    fn to_latex(&self) -> String {
+        match self {
+            DocumentDate::Today => String::from(DEF_TODAY),
+            DocumentDate::Custom(date) => escape_text(date),
+            DocumentDate::Empty => String::new()
+        }
+    }
+}
+
+/// The title page metadata of a `Document`.
+#[derive(Default)]
+pub(crate) struct Metadata {
+    pub(crate) title: String,
+    pub(crate) subtitle: String,
+    pub(crate) authors: Vec<Author>,
+    pub(crate) date: Option<DocumentDate>,
+    pub(crate) keywords: Vec<String>,
+    pub(crate) abstract_text: Option<Text>
+}
+
+impl Metadata {
+    pub(crate) fn update_indent(&mut self, super_indent: &usize) {
+        if let Some(abstract_text) = &mut self.abstract_text {
+            abstract_text.update_indent(super_indent);
+        }
+    }
+
+    pub(crate) fn required_packages(&self) -> Vec<Package> {
+        let mut packages = Vec::new();
+
+        if let Some(abstract_text) = &self.abstract_text {
+            packages.extend(abstract_text.required_packages());
+        }
+
+        if !self.get_pdf_info().is_empty() {
+            packages.push(Package::new(HYPERREF_PACKAGE, vec![]));
+        }
+
+        packages
+    }
+
+    pub(crate) fn check_references(&self, registry: &LabelRegistry) -> Result<(), RustexError> {
+        match &self.abstract_text {
+            Some(abstract_text) => abstract_text.check_references(registry),
+            None => Ok(())
+        }
+    }
+
+    /// Writes the title, authors, date and PDF metadata before the `\begin{document}` line.
+    pub(crate) fn build_preamble(&self, doc: &Document, class_type: &ClassType) -> Result<(), RustexError> {
+        let mut lines: Vec<String> = Vec::new();
+
+        if !self.title.is_empty() {
+            lines.push(format!("{}{}", DEF_TITLE, into_braces(&self.get_title(class_type))));
+        }
+
+        if !self.subtitle.is_empty() && matches!(class_type, ClassType::Beamer) {
+            lines.push(format!("{}{}", DEF_SUBTITLE, into_braces(&escape_text(&self.subtitle))));
+        }
+
+        if !self.authors.is_empty() {
+            lines.push(self.get_authors(class_type));
+        }
+
+        if let Some(date) = &self.date {
+            lines.push(format!("{}{}", DEF_DATE, into_braces(&date.to_latex())));
+        }
+
+        let pdf_info = self.get_pdf_info();
+
+        if !pdf_info.is_empty() {
+            let pdf_info_str = format!("pdfinfo={}", into_braces(&pdf_info.join(", ")));
+            lines.push(format!("{}{}", DEF_HYPERSETUP, into_braces(&pdf_info_str)));
+        }
+
+        if lines.is_empty() {
+            return Ok(());
+        }
+
+        writeln!(doc.get_writer(), "{}", TITLE_COMMENT)?;
+        for line in lines {
+            writeln!(doc.get_writer(), "{}", line)?;
+        }
+
+        doc.add_blank_line()
+    }
+
+    /// Writes the title page and the abstract after the `\begin{document}` line.
+    pub(crate) fn build_front_matter(&self, doc: &Document, class_type: &ClassType, indent: &usize) -> Result<(), RustexError> {
+        let inner_indent: &usize = &(indent + 1);
+
+        if !self.title.is_empty() {
+            match class_type {
+                ClassType::Beamer => {
+                    write_indented_line(doc, indent, DEF_BEGIN_FRAME)?;
+                    write_indented_line(doc, inner_indent, DEF_TITLE_PAGE)?;
+                    write_indented_line(doc, indent, DEF_END_FRAME)?;
+                }
+                ClassType::Letter => {}
+                _ => write_indented_line(doc, indent, DEF_MAKE_TITLE)?
+            }
+
+            doc.add_blank_line()?;
+        }
+
+        if let Some(abstract_text) = &self.abstract_text {
+            let (begin_str, end_str) = match class_type {
+                ClassType::Beamer => (format!("{}{}", DEF_BEGIN_FRAME, into_braces(&String::from(ABSTRACT_TITLE))),
+                                      String::from(DEF_END_FRAME)),
+                ClassType::Book => (format!("{}*{}", DEF_CHAPTER, into_braces(&String::from(ABSTRACT_TITLE))),
+                                    String::new()),
+                _ => (String::from(DEF_BEGIN_ABSTRACT), String::from(DEF_END_ABSTRACT))
+            };
+
+            write_indented_line(doc, indent, &begin_str)?;
+            doc.build_item(abstract_text)?;
+
+            if !self.keywords.is_empty() {
+                let keywords_str = format!("{} {}", DEF_KEYWORDS, escape_text(&self.keywords.join(", ")));
+                write_indented_line(doc, inner_indent, &keywords_str)?;
+            }
+
+            if !end_str.is_empty() {
+                write_indented_line(doc, indent, &end_str)?;
+            }
+
+            doc.add_blank_line()?;
+        }
+
+        Ok(())
+    }
+
+    pub(crate) fn validate(&self, class_type: &ClassType) -> Result<(), RustexError> {
+        if let ClassType::Letter = class_type {
+            if !self.subtitle.is_empty() {
+                return Err(RustexError::validation("a subtitle is not supported by the letter class"));
+            }
+
+            if self.abstract_text.is_some() {
+                return Err(RustexError::validation("an abstract is not supported by the letter class"));
+            }
+        }
+
+        if !self.subtitle.is_empty() && self.title.is_empty() {
+            return Err(RustexError::validation(&format!("subtitle '{}' is set without a title", self.subtitle)));
+        }
+
+        Ok(())
+    }
+
+    // Only `beamer` has a `\subtitle`, so other classes write it below the title
+    fn get_title(&self, class_type: &ClassType) -> String {
+        let title = escape_text(&self.title);
+
+        match self.subtitle.is_empty() || matches!(class_type, ClassType::Beamer) {
+            true => title,
+            false => format!("{} {} {} {}", title, DEF_LINE_BREAK, DEF_SUBTITLE_SIZE, escape_text(&self.subtitle))
+        }
+    }
+
+    // `beamer` also writes the authors in the footline, where only their names fit
+    fn get_authors(&self, class_type: &ClassType) -> String {
+        let authors: Vec<String> = self.authors.iter().map(|author| author.to_latex()).collect();
+
+        match class_type {
+            ClassType::Letter => {
+                format!("{}{}", DEF_SIGNATURE, into_braces(&authors.join(&format!(" {} ", DEF_LINE_BREAK))))
+            }
+            ClassType::Beamer => {
+                let names: Vec<String> = self.authors.iter().map(|author| escape_text(&author.name)).collect();
+                format!("{}{}{}", DEF_AUTHOR, into_brackets(&names.join(", ")),
+                        into_braces(&authors.join(&format!(" {} ", DEF_AUTHOR_SEPARATOR))))
+            }
+            _ => format!("{}{}", DEF_AUTHOR, into_braces(&authors.join(&format!(" {} ", DEF_AUTHOR_SEPARATOR))))
+        }
+    }
+
+    fn get_pdf_info(&self) -> Vec<String> {
+        let names: Vec<String> = self.authors.iter().map(|author| escape_text(&author.name)).collect();
+
+        [("Title", escape_text(&self.title)),
+         ("Subject", escape_text(&self.subtitle)),
+         ("Author", names.join(", ")),
+         ("Keywords", escape_text(&self.keywords.join(", ")))]
+            .into_iter()
+            .filter(|(_, value)| !value.is_empty())
+            .map(|(key, value)| format!("{}={}", key, into_braces(&value)))
+            .collect()
+    }
+}
@@ -1,4 +1,17 @@
 pub mod document;
+pub mod package;
 pub mod item;
 pub mod container;
-pub mod table;
\ No newline at end of file
+pub mod table;
+pub mod reference;
+pub mod math;
+pub mod expression;
+pub mod listing;
+pub mod graphics;
+pub mod asset;
+pub mod metadata;
+pub mod contents;
+pub mod columns;
+pub mod rules;
+pub mod csv;
+pub mod records;
\ No newline at end of file
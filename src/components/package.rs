@@ -0,0 +1,164 @@
+use std::io::Write;
+use super::document::*;
+use crate::error::RustexError;
+use crate::utilities::def_syntax::*;
+use crate::utilities::format::*;
+
+// Options of a package that cannot be used together
+const CONFLICTING_OPTIONS: [(&str, &[&str]); 5] = [
+    ("geometry", &["portrait", "landscape"]),
+    ("graphicx", &["draft", "final"]),
+    ("hyperref", &["draft", "final"]),
+    ("inputenc", &["utf8", "latin1", "ansinew"]),
+    ("xcolor", &["natural", "rgb", "cmy", "cmyk", "hsb", "gray", "RGB", "HTML", "HSB", "Gray"])
+];
+
+// Packages that must be loaded after the others, by loading order
+const LATE_PACKAGES: [&str; 2] = ["hyperref", "cleveref"];
+
+/// An object to import libraries to your LaTeX file.
+pub struct Package {
+    name: String,
+    options: Vec<String>
+}
+
+impl Package {
+    /// Initializes a new `Package` object
+    pub fn new(_name: &str, _options: Vec<&str>) -> Self {
+        Self {
+            name: String::from(_name),
+            options: _options
+                .iter()
+                .map(|&option| String::from(option))
+                .collect()
+        }
+    }
+
+    /// Gets the name of the `Package`.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    #[doc(hidden)]
+    pub fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        let mut package_str: String = String::from(DEF_PACKAGE);
+
+        if !self.options.is_empty() {
+            package_str = format!("{}{}", package_str, into_brackets(&self.options.join(", ")));
+        }
+
+        package_str = format!("{}{}", package_str, into_braces(&self.name));
+
+        writeln!(doc.get_writer(), "{}", package_str)?;
+        Ok(())
+    }
+
+    #[doc(hidden)]
+    pub fn describe(&self) -> String {
+        format!("Package '{}'", self.name)
+    }
+
+    fn merge_options(&mut self, other: &Package, warnings: &mut Vec<String>) {
+        for option in &other.options {
+            if self.options.contains(option) {
+                continue;
+            }
+
+            match self.find_option_value(option) {
+                Some(value) => push_warning(warnings, format!(
+                    "package '{}' is required with option '{}' and '{}', only the first one is kept",
+                    self.name, value, option
+                )),
+                None => self.options.push(option.clone())
+            }
+        }
+    }
+
+    // Finds an option with the same key as a `key=value` option
+    fn find_option_value(&self, option: &str) -> Option<&String> {
+        let key = option.split_once('=')?.0.trim();
+
+        self.options.iter().find(|existing_option| {
+            existing_option
+                .split_once('=')
+                .is_some_and(|(existing_key, _)| existing_key.trim() == key)
+        })
+    }
+
+    fn check_conflicts(&self, warnings: &mut Vec<String>) {
+        for (name, exclusive_options) in CONFLICTING_OPTIONS {
+            if self.name != name {
+                continue;
+            }
+
+            let used_options: Vec<&str> = exclusive_options
+                .iter()
+                .copied()
+                .filter(|option| self.options.iter().any(|used_option| used_option == option))
+                .collect();
+
+            if used_options.len() > 1 {
+                push_warning(warnings, format!(
+                    "package '{}' is used with conflicting options: {}",
+                    self.name, used_options.join(", ")
+                ));
+            }
+        }
+    }
+
+    /// Checks whether the `Package` must be loaded after every other package, e.g. `hyperref`.
+    pub(crate) fn is_late(&self) -> bool {
+        self.load_order() > 0
+    }
+
+    fn load_order(&self) -> usize {
+        LATE_PACKAGES
+            .iter()
+            .position(|name| *name == self.name)
+            .map_or(0, |position| position + 1)
+    }
+}
+
+/// Resolves the `Packages` required by the `Items` of a `Document`.
+/// Required packages are de-duplicated, their options are merged and they are ordered
+/// so that packages such as `hyperref` are loaded last.
+/// The `Document` writes these late packages after the packages added by the user.
+/// Packages added by the user take precedence and are not part of the result.
+/// Returns the packages to load and the warnings raised during the resolution.
+pub(crate) fn resolve_packages(added: &[Package], required: Vec<Package>) -> (Vec<Package>, Vec<String>) {
+    let mut resolved: Vec<Package> = Vec::new();
+    let mut warnings: Vec<String> = Vec::new();
+
+    for package in required {
+        if let Some(added_package) = added.iter().find(|added_package| added_package.name == package.name) {
+            for option in &package.options {
+                if !added_package.options.contains(option) {
+                    push_warning(&mut warnings, format!(
+                        "package '{}' is required with option '{}', which is missing from the added package",
+                        package.name, option
+                    ));
+                }
+            }
+
+            continue;
+        }
+
+        match resolved.iter_mut().find(|resolved_package| resolved_package.name == package.name) {
+            Some(resolved_package) => resolved_package.merge_options(&package, &mut warnings),
+            None => resolved.push(package)
+        }
+    }
+
+    for package in added.iter().chain(resolved.iter()) {
+        package.check_conflicts(&mut warnings);
+    }
+
+    resolved.sort_by_key(|package| package.load_order());
+    (resolved, warnings)
+}
+
+fn push_warning(warnings: &mut Vec<String>, warning: String) {
+    if !warnings.contains(&warning) {
+        warnings.push(warning);
+    }
+}
@@ -0,0 +1,90 @@
+use std::fmt::Display;
+use super::item::*;
+use super::table::*;
+use super::csv::*;
+
+/// A record written as a row of a `Table`.
+/// Refer to `Table::from_records` for more information.
+///
+/// With the `derive` feature, `LatexTable` is derived for structs with named fields.
+/// Every field becomes a column, and must implement `Display`.
+/// Fields accept the `#[latex(...)]` attribute with the following options:
+/// - `header = "..."`: the header of the column, the name of the field by default.
+/// - `align = "left" | "center" | "right"`: the alignment of the column,
+///   on the left by default, or on the right for a column with a number format.
+/// - `format = "decimals(2)" | "thousands(2)" | "percent(1)" | "scientific(2)"`: the `NumberFormat` of the column.
+/// - `order = 1`: the position of the column, numbered from 0. The columns without `order`
+///   fill the remaining positions in the order of their fields.
+/// - `skip`: the field is not written in the `Table`.
+///
+/// Example:
+///
+#[cfg_attr(feature = "derive", doc = "```rust")]
+#[cfg_attr(not(feature = "derive"), doc = "```rust,ignore")]
+/// # use rustex::*;
+/// #[derive(LatexTable)]
+/// struct Measurement {
+///     #[latex(header = "Sample", order = 0)]
+///     name: String,
+///     #[latex(header = "Yield", format = "percent(1)", order = 2)]
+///     ratio: f64,
+///     #[latex(header = "Mass (g)", format = "decimals(2)", order = 1)]
+///     mass: f64,
+///     #[latex(skip)]
+///     operator: String
+/// }
+///
+/// let measurements = vec![
+///     Measurement { name: String::from("A_1"), ratio: 0.125, mass: 12.0, operator: String::from("Gabriel") },
+///     Measurement { name: String::from("B_2"), ratio: 0.5, mass: 3.456, operator: String::from("Gabriel") }
+/// ];
+///
+/// let table = Table::from_records(&measurements, "H", true, None, "tab_measurements");
+/// ```
+///
+/// Generated LaTeX:
+///
+/// ```tex
+/// \begin{table}[H]
+///     \centering
+///     \begin{tabular}{|l|r|r|}
+///         \hline
+///         \textbf{Sample} & \textbf{Mass (g)} & \textbf{Yield} \\
+///         \hline
+///         A\_1 & 12.00 & 12.5\% \\
+///         B\_2 & 3.46 & 50.0\% \\
+///         \hline
+///     \end{tabular}
+///     \label{tab_measurements}
+/// \end{table}
+/// ```
+pub trait LatexTable {
+    /// Gets the headers of the columns, in order.
+    fn get_headers() -> Vec<String>;
+
+    /// Gets the alignments of the columns, in order.
+    fn get_alignments() -> Vec<CellAlignment>;
+
+    /// Gets the cells of the record, in the order of the columns.
+    fn get_cells(&self) -> Vec<Text>;
+}
+
+/// Writes a value as an escaped `Text`, or as a formatted number.
+/// Used by the code generated by `#[derive(LatexTable)]`.
+#[doc(hidden)]
+pub fn format_cell<T: Display + ?Sized>(value: &T, format: Option<NumberFormat>) -> Text {
+    format_field(&value.to_string(), format)
+}
+
+impl Table {
+    /// Initializes a new `Table` object with a row for every record.
+    /// The header row is written in bold between horizontal lines, and every cell is escaped.
+    pub fn from_records<R: LatexTable>(_records: &[R], _positioning: &str, _centered: bool,
+                                       _caption: Option<Text>, _label: &str) -> Self {
+        let headers = R::get_headers();
+        let rows: Vec<Vec<Text>> = _records.iter().map(|record| record.get_cells()).collect();
+
+        Table::from_rows(_positioning, &R::get_alignments(), Some(&headers[..]), rows,
+                         _centered, _caption, _label)
+    }
+}
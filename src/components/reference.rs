@@ -0,0 +1,153 @@
+use std::fmt;
+use crate::error::RustexError;
+use crate::utilities::def_syntax::*;
+
+/// A handle to the label of a `Section`, a `Chapter`, a `Figure`, a `Table` or an `Enumerate`.
+/// Can be referenced inside a `Text` with `Ref`, `PageRef`, `EqRef` or `AutoRef`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Label {
+    name: String
+}
+
+impl Label {
+    /// Initializes a new `Label` object
+    pub fn new(_name: &str) -> Self {
+        Self {
+            name: String::from(_name)
+        }
+    }
+
+    /// Gets the name of the `Label`.
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// A reference to the number of a `Label` (`\ref`).
+/// References are embedded in a `Text` with `format!`.
+///
+/// Example:
+///
+/// ```rust
+/// # use rustex::*;
+/// let figure = Figure::new("H", true, "plot.png", "scale=0.5", None, "fig_1");
+/// let text = Text::new(&format!("See figure {} on page {}.",
+///                               Ref::new(&figure.get_label()),
+///                               PageRef::new(&figure.get_label())));
+///
+/// assert_eq!(text.get_string(), r"See figure \ref{fig_1} on page \pageref{fig_1}.");
+/// ```
+pub struct Ref {
+    label: Label
+}
+
+/// A reference to the page of a `Label` (`\pageref`).
+pub struct PageRef {
+    label: Label
+}
+
+/// A reference to the number of an equation, in parentheses (`\eqref`).
+/// The `amsmath` package is added to the `Document` automatically.
+pub struct EqRef {
+    label: Label
+}
+
+/// A reference to a `Label` preceded by the name of its type, e.g. "Figure 1" (`\autoref`).
+/// The `hyperref` package is added to the `Document` automatically.
+pub struct AutoRef {
+    label: Label
+}
+
+impl Ref {
+    /// Initializes a new `Ref` object
+    pub fn new(_label: &Label) -> Self {
+        Self { label: _label.clone() }
+    }
+}
+
+impl PageRef {
+    /// Initializes a new `PageRef` object
+    pub fn new(_label: &Label) -> Self {
+        Self { label: _label.clone() }
+    }
+}
+
+impl EqRef {
+    /// Initializes a new `EqRef` object
+    pub fn new(_label: &Label) -> Self {
+        Self { label: _label.clone() }
+    }
+}
+
+impl AutoRef {
+    /// Initializes a new `AutoRef` object
+    pub fn new(_label: &Label) -> Self {
+        Self { label: _label.clone() }
+    }
+}
+
+// A reference is written in a `Text` as `@ref{label}`, which is parsed back by the markup parser
+impl fmt::Display for Ref {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "@{}{{{}}}", MARKUP_REF, self.label.name)
+    }
+}
+
+impl fmt::Display for PageRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "@{}{{{}}}", MARKUP_PAGE_REF, self.label.name)
+    }
+}
+
+impl fmt::Display for EqRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "@{}{{{}}}", MARKUP_EQ_REF, self.label.name)
+    }
+}
+
+impl fmt::Display for AutoRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "@{}{{{}}}", MARKUP_AUTO_REF, self.label.name)
+    }
+}
+
+/// Every `Label` defined in a `Document`.
+/// Used to detect duplicate labels and references to undefined labels.
+#[doc(hidden)]
+#[derive(Default)]
+pub struct LabelRegistry {
+    labels: Vec<Label>
+}
+
+impl LabelRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a `Label`, or returns an error if it is already defined.
+    /// Empty labels are ignored.
+    pub fn register(&mut self, label: &Label) -> Result<(), RustexError> {
+        if label.name.is_empty() {
+            return Ok(());
+        }
+
+        if self.contains(label) {
+            return Err(RustexError::validation(&format!("duplicate label '{}'", label.name)));
+        }
+
+        self.labels.push(label.clone());
+        Ok(())
+    }
+
+    /// Returns an error if the referenced `Label` is not defined.
+    pub fn check_reference(&self, label: &Label) -> Result<(), RustexError> {
+        match self.contains(label) {
+            true => Ok(()),
+            false => Err(RustexError::validation(&format!("reference to undefined label '{}'", label.name)))
+        }
+    }
+
+    fn contains(&self, label: &Label) -> bool {
+        self.labels.contains(label)
+    }
+}
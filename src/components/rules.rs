@@ -0,0 +1,522 @@
+use super::document::*;
+use super::graphics::Length;
+use super::item::*;
+use super::package::*;
+use super::table::*;
+use crate::error::RustexError;
+use crate::utilities::def_syntax::*;
+use crate::utilities::format::*;
+
+/// Defines the rules written around the header and the body of a `Table`.
+///
+/// Example:
+///
+/// ```rust
+/// # use rustex::*;
+/// let mut table = Table::from_columns("H", ColumnSpec::new().left().right().right(), true, None, "tab_sales");
+/// table.set_style(TableStyle::Booktabs);
+/// table.set_header_rows(2);
+///
+/// let mut year_cell = TableCell::new(Text::new("Year"));
+/// year_cell.set_column_span(2);
+///
+/// table.add_component(TableRow::from_cells(vec![TableCell::new(Text::new("")), year_cell]));
+///
+/// let mut year_rule = PartialRule::new(2, 3);
+/// year_rule.set_trim(true, true);
+/// table.add_component(year_rule);
+///
+/// table.add_component(TableRow::new(vec![Text::new("Product"), Text::new("2023"), Text::new("2024")]));
+/// table.add_component(TableRow::new(vec![Text::new("Widget"), Text::new("12"), Text::new("15")]));
+/// table.add_component(LineSpace::new());
+/// table.add_component(TableRow::new(vec![Text::new("Gadget"), Text::new("7"), Text::new("9")]));
+/// ```
+///
+/// Generated LaTeX:
+///
+/// ```tex
+/// \begin{table}[H]
+///     \centering
+///     \begin{tabular}{lrr}
+///         \toprule
+///          & \multicolumn{2}{c}{Year} \\
+///         \cmidrule(lr){2-3}
+///         Product & 2023 & 2024 \\
+///         \midrule
+///         Widget & 12 & 15 \\
+///         \addlinespace
+///         Gadget & 7 & 9 \\
+///         \bottomrule
+///     \end{tabular}
+///     \label{tab_sales}
+/// \end{table}
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TableStyle {
+    /// `\hline` above and below the header and below the body, with vertical rules in generated tables.
+    Classic,
+    /// `\toprule`, `\midrule` and `\bottomrule` (`booktabs` package), without vertical rules.
+    Booktabs
+}
+
+impl TableStyle {
+    pub fn get_top_rule(&self) -> &str {
+        match self {
+            TableStyle::Classic => DEF_HORIZONTAL_LINE,
+            TableStyle::Booktabs => DEF_TOP_RULE
+        }
+    }
+
+    pub fn get_mid_rule(&self) -> &str {
+        match self {
+            TableStyle::Classic => DEF_HORIZONTAL_LINE,
+            TableStyle::Booktabs => DEF_MID_RULE
+        }
+    }
+
+    pub fn get_bottom_rule(&self) -> &str {
+        match self {
+            TableStyle::Classic => DEF_HORIZONTAL_LINE,
+            TableStyle::Booktabs => DEF_BOTTOM_RULE
+        }
+    }
+
+    pub(crate) fn required_packages(&self) -> Vec<Package> {
+        match self {
+            TableStyle::Classic => vec![],
+            TableStyle::Booktabs => booktabs_package()
+        }
+    }
+}
+
+fn booktabs_package() -> Vec<Package> {
+    vec![Package::new(BOOKTABS_PACKAGE, vec![])]
+}
+
+fn validate_column_range(first_column: usize, last_column: usize) -> Result<(), RustexError> {
+    match first_column == 0 || first_column > last_column {
+        true => Err(RustexError::validation(&format!(
+            "invalid column range {}-{}, columns are numbered from 1", first_column, last_column
+        ))),
+        false => Ok(())
+    }
+}
+
+fn into_column_range(first_column: usize, last_column: usize) -> Result<String, RustexError> {
+    validate_column_range(first_column, last_column)?;
+    Ok(into_braces(&format!("{}-{}", first_column, last_column)))
+}
+
+fn check_column_range(first_column: usize, last_column: usize, columns: usize) -> Result<(), RustexError> {
+    validate_column_range(first_column, last_column)?;
+
+    match last_column <= columns {
+        true => Ok(()),
+        false => Err(RustexError::validation(&format!(
+            "rule ends at column {} but the table has {} column(s)", last_column, columns
+        )))
+    }
+}
+
+/// A horizontal line below some columns of a `Table` (`\cline`).
+pub struct PartialLine {
+    first_column: usize,
+    last_column: usize,
+    indent: usize
+}
+
+impl PartialLine {
+    /// Initializes a new `PartialLine` object, from the first to the last column included, numbered from 1
+    pub fn new(_first_column: usize, _last_column: usize) -> Self {
+        Self {
+            first_column: _first_column,
+            last_column: _last_column,
+            indent: 0
+        }
+    }
+}
+
+impl Item for PartialLine {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        let line_str = format!("{}{}", DEF_PARTIAL_LINE, into_column_range(self.first_column, self.last_column)?);
+        write_indented_line(doc, &self.indent, &line_str)
+    }
+
+    fn update_indent(&mut self, super_indent: &usize) {
+        self.indent = super_indent + 1;
+    }
+
+    fn describe(&self) -> String {
+        String::from("PartialLine")
+    }
+
+    fn validate(&self) -> Result<(), RustexError> {
+        validate_column_range(self.first_column, self.last_column)
+    }
+}
+
+impl TableComponent for PartialLine {
+    fn check_columns(&self, columns: usize) -> Result<(), RustexError> {
+        check_column_range(self.first_column, self.last_column, columns)
+    }
+}
+
+/// The rule above the header of a `Table` (`\toprule`). The `booktabs` package is added automatically.
+pub struct TopRule {
+    indent: usize
+}
+
+impl TopRule {
+    /// Initializes a new `TopRule` object
+    pub fn new() -> Self {
+        Self {
+            indent: 0
+        }
+    }
+}
+
+impl Default for TopRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Item for TopRule {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        write_indented_line(doc, &self.indent, DEF_TOP_RULE)
+    }
+
+    fn update_indent(&mut self, super_indent: &usize) {
+        self.indent = super_indent + 1;
+    }
+
+    fn describe(&self) -> String {
+        String::from("TopRule")
+    }
+
+    fn required_packages(&self) -> Vec<Package> {
+        booktabs_package()
+    }
+}
+
+impl TableComponent for TopRule {}
+
+/// The rule between the header and the body of a `Table` (`\midrule`). The `booktabs` package is added automatically.
+pub struct MidRule {
+    indent: usize
+}
+
+impl MidRule {
+    /// Initializes a new `MidRule` object
+    pub fn new() -> Self {
+        Self {
+            indent: 0
+        }
+    }
+}
+
+impl Default for MidRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Item for MidRule {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        write_indented_line(doc, &self.indent, DEF_MID_RULE)
+    }
+
+    fn update_indent(&mut self, super_indent: &usize) {
+        self.indent = super_indent + 1;
+    }
+
+    fn describe(&self) -> String {
+        String::from("MidRule")
+    }
+
+    fn required_packages(&self) -> Vec<Package> {
+        booktabs_package()
+    }
+}
+
+impl TableComponent for MidRule {}
+
+/// The rule below the body of a `Table` (`\bottomrule`). The `booktabs` package is added automatically.
+pub struct BottomRule {
+    indent: usize
+}
+
+impl BottomRule {
+    /// Initializes a new `BottomRule` object
+    pub fn new() -> Self {
+        Self {
+            indent: 0
+        }
+    }
+}
+
+impl Default for BottomRule {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Item for BottomRule {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        write_indented_line(doc, &self.indent, DEF_BOTTOM_RULE)
+    }
+
+    fn update_indent(&mut self, super_indent: &usize) {
+        self.indent = super_indent + 1;
+    }
+
+    fn describe(&self) -> String {
+        String::from("BottomRule")
+    }
+
+    fn required_packages(&self) -> Vec<Package> {
+        booktabs_package()
+    }
+}
+
+impl TableComponent for BottomRule {}
+
+/// A rule below some columns of a `Table` (`\cmidrule`), e.g. below a cell spanning several columns.
+/// The `booktabs` package is added automatically.
+pub struct PartialRule {
+    first_column: usize,
+    last_column: usize,
+    left_trim: bool,
+    right_trim: bool,
+    indent: usize
+}
+
+impl PartialRule {
+    /// Initializes a new `PartialRule` object, from the first to the last column included, numbered from 1
+    pub fn new(_first_column: usize, _last_column: usize) -> Self {
+        Self {
+            first_column: _first_column,
+            last_column: _last_column,
+            left_trim: false,
+            right_trim: false,
+            indent: 0
+        }
+    }
+
+    /// Shortens the `PartialRule` on the left and on the right (`(lr)`),
+    /// to separate it from the rules of the neighbouring columns.
+    pub fn set_trim(&mut self, left: bool, right: bool) {
+        self.left_trim = left;
+        self.right_trim = right;
+    }
+}
+
+impl Item for PartialRule {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        let trim = format!("{}{}", if self.left_trim { "l" } else { "" }, if self.right_trim { "r" } else { "" });
+        let trim_str = match trim.is_empty() {
+            true => trim,
+            false => format!("({})", trim)
+        };
+
+        let rule_str = format!("{}{}{}", DEF_PARTIAL_RULE, trim_str,
+                               into_column_range(self.first_column, self.last_column)?);
+        write_indented_line(doc, &self.indent, &rule_str)
+    }
+
+    fn update_indent(&mut self, super_indent: &usize) {
+        self.indent = super_indent + 1;
+    }
+
+    fn describe(&self) -> String {
+        String::from("PartialRule")
+    }
+
+    fn validate(&self) -> Result<(), RustexError> {
+        validate_column_range(self.first_column, self.last_column)
+    }
+
+    fn required_packages(&self) -> Vec<Package> {
+        booktabs_package()
+    }
+}
+
+impl TableComponent for PartialRule {
+    fn check_columns(&self, columns: usize) -> Result<(), RustexError> {
+        check_column_range(self.first_column, self.last_column, columns)
+    }
+}
+
+/// An extra vertical space between two rows of a `Table` (`\addlinespace`), to group rows without a rule.
+/// The `booktabs` package is added automatically.
+pub struct LineSpace {
+    space: Option<Length>,
+    indent: usize
+}
+
+impl LineSpace {
+    /// Initializes a new `LineSpace` object, with the default space of `booktabs`
+    pub fn new() -> Self {
+        Self {
+            space: None,
+            indent: 0
+        }
+    }
+
+    pub fn set_space(&mut self, space: Length) {
+        self.space = Some(space);
+    }
+}
+
+impl Default for LineSpace {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Item for LineSpace {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        let space_str = match &self.space {
+            Some(space) => format!("{}{}", DEF_LINE_SPACE, into_brackets(&space.to_string())),
+            None => String::from(DEF_LINE_SPACE)
+        };
+
+        write_indented_line(doc, &self.indent, &space_str)
+    }
+
+    fn update_indent(&mut self, super_indent: &usize) {
+        self.indent = super_indent + 1;
+    }
+
+    fn describe(&self) -> String {
+        String::from("LineSpace")
+    }
+
+    fn required_packages(&self) -> Vec<Package> {
+        booktabs_package()
+    }
+}
+
+impl TableComponent for LineSpace {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::components::columns::*;
+    use crate::components::csv::*;
+
+    // Renders the `Table` and returns the trimmed lines inside its `tabular`
+    fn tabular_lines(table: Table) -> Vec<String> {
+        let mut doc = Document::from_writer(Vec::new(), DocumentClass::new(ClassType::Article, vec![]));
+        doc.add_item(table);
+
+        let latex = doc.render_to_string().unwrap();
+        let lines: Vec<String> = latex.lines().map(|line| String::from(line.trim())).collect();
+        let begin = lines.iter().position(|line| line.starts_with(DEF_BEGIN_TABULAR)).unwrap();
+        let end = lines.iter().position(|line| line == DEF_END_TABULAR).unwrap();
+
+        lines[begin..=end].to_vec()
+    }
+
+    fn row(cells: &[&str]) -> TableRow {
+        TableRow::new(cells.iter().map(|cell| Text::new(cell)).collect())
+    }
+
+    #[test]
+    fn places_booktabs_rules_around_the_header_and_manual_rules() {
+        let mut table = Table::from_columns("H", ColumnSpec::new().left().right().right(), false, None, "tab_sales");
+        table.set_style(TableStyle::Booktabs);
+        table.set_header_rows(2);
+
+        let mut year_cell = TableCell::new(Text::new("Year"));
+        year_cell.set_column_span(2);
+        table.add_component(TableRow::from_cells(vec![TableCell::new(Text::new("")), year_cell]));
+
+        let mut year_rule = PartialRule::new(2, 3);
+        year_rule.set_trim(true, true);
+        table.add_component(year_rule);
+
+        table.add_component(row(&["Product", "2023", "2024"]));
+        table.add_component(PartialRule::new(1, 1));
+        table.add_component(row(&["Widget", "12", "15"]));
+        table.add_component(LineSpace::new());
+        table.add_component(row(&["Gadget", "7", "9"]));
+
+        assert_eq!(tabular_lines(table), vec![
+            r"\begin{tabular}{lrr}",
+            r"\toprule",
+            r"& \multicolumn{2}{c}{Year} \\",
+            r"\cmidrule(lr){2-3}",
+            r"Product & 2023 & 2024 \\",
+            r"\midrule",
+            r"\cmidrule{1-1}",
+            r"Widget & 12 & 15 \\",
+            r"\addlinespace",
+            r"Gadget & 7 & 9 \\",
+            r"\bottomrule",
+            r"\end{tabular}"
+        ]);
+    }
+
+    #[test]
+    fn writes_no_mid_rule_without_header_rows() {
+        let mut table = Table::from_columns("H", ColumnSpec::new().left().left(), false, None, "tab_1");
+        table.set_style(TableStyle::Booktabs);
+        table.add_component(row(&["a", "b"]));
+        table.add_component(row(&["c", "d"]));
+
+        assert_eq!(tabular_lines(table), vec![
+            r"\begin{tabular}{ll}",
+            r"\toprule",
+            r"a & b \\",
+            r"c & d \\",
+            r"\bottomrule",
+            r"\end{tabular}"
+        ]);
+    }
+
+    #[test]
+    fn switches_generated_tables_to_booktabs() {
+        let csv = "name,quantity\nWidget,12\nGadget,7\n";
+        let mut table = Table::from_csv_reader(csv.as_bytes(), &CsvOptions::new(), "H", false, None, "tab_csv").unwrap();
+        table.set_style(TableStyle::Booktabs);
+
+        let lines = tabular_lines(table);
+
+        assert!(!lines[0].contains(DEF_VERTICAL_RULE), "{}", lines[0]);
+        assert_eq!(lines[1..], [
+            r"\toprule",
+            r"\textbf{name} & \textbf{quantity} \\",
+            r"\midrule",
+            r"Widget & 12 \\",
+            r"Gadget & 7 \\",
+            r"\bottomrule",
+            r"\end{tabular}"
+        ]);
+    }
+
+    #[test]
+    fn rejects_invalid_column_ranges() {
+        let ranges: [(Box<dyn TableComponent>, &str); 4] = [
+            (Box::new(PartialRule::new(0, 2)), "invalid column range 0-2, columns are numbered from 1"),
+            (Box::new(PartialLine::new(3, 2)), "invalid column range 3-2, columns are numbered from 1"),
+            (Box::new(PartialRule::new(2, 4)), "rule ends at column 4 but the table has 3 column(s)"),
+            (Box::new(PartialLine::new(0, 4)), "invalid column range 0-4, columns are numbered from 1")
+        ];
+
+        for (component, message) in ranges {
+            let error = component.check_columns(3).unwrap_err();
+            assert_eq!(error.to_string(), format!("validation error: {}", message));
+        }
+
+        assert!(PartialLine::new(1, 3).check_columns(3).is_ok());
+    }
+
+    #[test]
+    fn rejects_reversed_ranges_in_tables_with_unknown_columns() {
+        let mut table = Table::new("H", "lX", false, None, "tab_1");
+        table.add_component(PartialRule::new(2, 1));
+
+        assert!(table.validate().unwrap_err().to_string().ends_with("invalid column range 2-1, columns are numbered from 1"));
+    }
+}
@@ -1,5 +1,9 @@
-use std::io::Error;
+use super::columns::*;
 use super::document::*;
+use super::package::*;
+use super::reference::*;
+use super::rules::*;
+use crate::error::RustexError;
 use super::item::*;
 use super::container::Container;
 use crate::utilities::def_syntax::*;
@@ -9,6 +13,10 @@ use crate::utilities::format::*;
 /// An object that can contain different `TableComponents` in rows and columns.
 /// Refer to `table` in LaTeX documentation for more information.
 ///
+/// The number of columns is read from the options, and every `TableRow` must span exactly that many columns.
+/// Use `Table::from_columns` with a `ColumnSpec` to set the options with types.
+/// Set a `TableStyle` to write the rules around the header and the body automatically.
+///
 /// Example:
 ///
 /// ```rust
@@ -22,10 +30,12 @@ use crate::utilities::format::*;
 /// );
 ///
 /// table_1.add_component(HorizontalLine::new());
-
-/// let table_row_1 = TableRow::new(vec![Text::new(r"\multicolumn{3}{|c|}{**Lorem Ipsum**}")]);
 ///
-/// table_1.add_component(table_row_1);
+/// let mut title_cell = TableCell::new(Text::new("**Lorem Ipsum**"));
+/// title_cell.set_column_span(3);
+/// title_cell.set_vertical_rules(true, true);
+///
+/// table_1.add_component(TableRow::from_cells(vec![title_cell]));
 /// table_1.add_component(HorizontalLine::new());
 ///
 /// let table_row_2 = TableRow::new(vec![
@@ -42,7 +52,7 @@ use crate::utilities::format::*;
 ///     Text::new("Test 2"),
 ///     Text::new("Test 3")]
 /// );
-
+///
 /// table_1.add_component(table_row_3);
 /// table_1.add_component(HorizontalLine::new());
 /// ```
@@ -50,7 +60,7 @@ use crate::utilities::format::*;
 /// Generated LaTeX:
 ///
 /// ```tex
-/// \begin{table}[H] \label{tab_1}
+/// \begin{table}[H]
 ///     \centering
 ///     \begin{tabular}{|c|c|c|}
 ///         \hline
@@ -61,12 +71,16 @@ use crate::utilities::format::*;
 ///         Test 1 & Test 2 & Test 3 \\
 ///         \hline
 ///     \end{tabular}
-///     \caption{Random caption}
+///     \caption{Random caption} \label{tab_1}
 /// \end{table}
 /// ```
 pub struct Table {
     positioning: String,
     options: String,
+    column_spec: Option<ColumnSpec>,
+    generated_alignments: Option<Vec<CellAlignment>>,
+    style: Option<TableStyle>,
+    header_rows: usize,
     components: Vec<Box<dyn TableComponent>>,
     centered: bool,
     caption: Option<Text>,
@@ -81,6 +95,10 @@ impl Table {
         Self {
             positioning: String::from(_positioning),
             options: String::from(_options),
+            column_spec: None,
+            generated_alignments: None,
+            style: None,
+            header_rows: 0,
             components: Vec::new(),
             centered: _centered,
             caption: _caption,
@@ -89,56 +107,154 @@ impl Table {
         }
     }
 
+    /// Initializes a new `Table` object with a `ColumnSpec`
+    pub fn from_columns(_positioning: &str, _columns: ColumnSpec, _centered: bool,
+                        _caption: Option<Text>, _label: &str) -> Self {
+        let mut table = Self::new(_positioning, &_columns.to_string(), _centered, _caption, _label);
+        table.column_spec = Some(_columns);
+        table
+    }
+
+    // Rows generated from data are written with the classic style, below a bold header row
+    pub(crate) fn from_rows(_positioning: &str, alignments: &[CellAlignment], headers: Option<&[String]>,
+                            rows: Vec<Vec<Text>>, _centered: bool, _caption: Option<Text>, _label: &str) -> Self {
+        let mut table = Self::new(_positioning, "", _centered, _caption, _label);
+        table.generated_alignments = Some(alignments.to_vec());
+        table.set_style(TableStyle::Classic);
+
+        if let Some(headers) = headers {
+            let header_cells: Vec<Text> = headers
+                .iter()
+                .map(|header| Text::raw(&into_bold(&escape_text(header))))
+                .collect();
+
+            table.add_component(TableRow::new(header_cells));
+            table.set_header_rows(1);
+        }
+
+        for row in rows {
+            table.add_component(TableRow::new(row));
+        }
+
+        table
+    }
+
+    /// Sets the rules written above the header, between the header and the body, and below the body.
+    /// The vertical rules of a `Table` generated from data follow the `TableStyle`.
+    pub fn set_style(&mut self, style: TableStyle) {
+        self.style = Some(style);
+
+        if let Some(alignments) = &self.generated_alignments {
+            let mut column_spec = ColumnSpec::new();
+
+            for alignment in alignments {
+                column_spec = match style {
+                    TableStyle::Classic => column_spec.rule().align(*alignment),
+                    TableStyle::Booktabs => column_spec.align(*alignment)
+                };
+            }
+
+            if style == TableStyle::Classic {
+                column_spec = column_spec.rule();
+            }
+
+            self.options = column_spec.to_string();
+            self.column_spec = Some(column_spec);
+        }
+    }
+
+    /// Sets the number of `TableRows` forming the header, followed by the middle rule of the `TableStyle`.
+    pub fn set_header_rows(&mut self, header_rows: usize) {
+        self.header_rows = header_rows;
+    }
+
+    // The options of a `ColumnSpec` are always counted, unlike raw options with user-defined column types
+    fn get_column_count(&self) -> Option<usize> {
+        match &self.column_spec {
+            Some(column_spec) => Some(column_spec.get_column_count()),
+            None => count_columns(&self.options)
+        }
+    }
+
     /// Add a `TableComponent` to the `Table`
     pub fn add_component<TC: TableComponent + 'static>(&mut self, component: TC) {
         self.components.push(Box::new(component));
     }
 
-    fn build_header(&self, doc: &Document) -> Result<(), Error> {
-        let mut begin_table_str = format!("{}{}", DEF_BEGIN_TABLE, into_brackets(&self.positioning));
-        begin_table_str = format!("{} {}", begin_table_str, into_label(&self.label));
-        write_indented_line(&doc, &self.indent, &begin_table_str)
+    /// Gets the `Label` of the `Table`, to be referenced in a `Text`.
+    pub fn get_label(&self) -> Label {
+        Label::new(&self.label)
+    }
+
+    fn build_header(&self, doc: &Document) -> Result<(), RustexError> {
+        let begin_table_str = format!("{}{}", DEF_BEGIN_TABLE, into_brackets(&self.positioning));
+        write_indented_line(doc, &self.indent, &begin_table_str)
     }
 
-    fn build_tabular(&self, doc: &Document, inner_indent: &usize) -> Result<(), Error> {
+    fn build_tabular(&self, doc: &Document, inner_indent: &usize) -> Result<(), RustexError> {
         let begin_tabular_str = format!("{}{}", DEF_BEGIN_TABULAR, into_braces(&self.options));
-        write_indented_line(&doc, inner_indent, &begin_tabular_str)?;
+        write_indented_line(doc, inner_indent, &begin_tabular_str)?;
+
+        let rule_indent = &(inner_indent + 1);
+
+        if let Some(style) = &self.style {
+            write_indented_line(doc, rule_indent, style.get_top_rule())?;
+        }
+
+        let mut rows = 0;
 
         for component in &self.components {
-            component.build(&doc)?;
+            doc.build_item(component.as_ref())?;
+
+            if component.is_row() {
+                rows += 1;
+
+                match &self.style {
+                    Some(style) if rows == self.header_rows => {
+                        write_indented_line(doc, rule_indent, style.get_mid_rule())?;
+                    }
+                    _ => {}
+                }
+            }
         }
 
-        write_indented_line(&doc, inner_indent, DEF_END_TABULAR)
+        if let Some(style) = &self.style {
+            write_indented_line(doc, rule_indent, style.get_bottom_rule())?;
+        }
+
+        write_indented_line(doc, inner_indent, DEF_END_TABULAR)
     }
 
-    fn build_caption(&self, doc: &Document, inner_indent: &usize) -> Result<(), Error> {
+    // The label follows the caption so that references point to the table number
+    fn build_caption(&self, doc: &Document, inner_indent: &usize) -> Result<(), RustexError> {
         match &self.caption {
             Some(caption) => {
-                let caption_str = format!("{}{}", DEF_CAPTION, into_braces(&caption.get_string()));
-                write_indented_line(&doc, inner_indent, &caption_str)?;
+                let caption_str = format!("{}{} {}", DEF_CAPTION, into_braces(&caption.render_inline()?),
+                                          into_label(&self.label));
+                write_indented_line(doc, inner_indent, &caption_str)
             }
-            None => {}
+            None => write_indented_line(doc, inner_indent, &into_label(&self.label))
         }
-
-        Ok(())
     }
 
-    fn build_centering(&self, doc: &Document, inner_indent: &usize) -> Result<(), Error> {
+    fn build_centering(&self, doc: &Document, inner_indent: &usize) -> Result<(), RustexError> {
         if self.centered {
-            write_indented_line(&doc, inner_indent,DEF_CENTERING )?;
+            write_indented_line(doc, inner_indent,DEF_CENTERING )?;
         }
 
         Ok(())
     }
 
-    fn build_end(&self, doc: &Document) -> Result<(), Error> {
-        write_indented_line(&doc, &self.indent, DEF_END_TABLE)?;
+    fn build_end(&self, doc: &Document) -> Result<(), RustexError> {
+        write_indented_line(doc, &self.indent, DEF_END_TABLE)?;
         doc.add_blank_line()
     }
 }
 
 impl Item for Table {
-    fn build(&self, doc: &Document) -> Result<(), Error> {
+    // Validated again here, so that a `Table` built by a custom `Item` is never written partially
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        self.validate()?;
         let inner_indent = &(self.indent + 1);
 
         self.build_header(doc)?;
@@ -152,6 +268,81 @@ impl Item for Table {
         self.indent= super_indent + 1;
         self.update_nested_indent();
     }
+
+    fn describe(&self) -> String {
+        format!("Table '{}'", self.label)
+    }
+
+    fn validate(&self) -> Result<(), RustexError> {
+        if let Some(column_spec) = &self.column_spec {
+            column_spec.validate()?;
+        }
+
+        if let Some(caption) = &self.caption {
+            caption.validate_inline()?;
+        }
+
+        let row_count = self.components.iter().filter(|component| component.is_row()).count();
+
+        if self.header_rows > row_count {
+            return Err(RustexError::validation(&format!(
+                "header has {} row(s) but the table has {} row(s)", self.header_rows, row_count
+            )));
+        }
+
+        let columns = self.get_column_count();
+
+        for component in &self.components {
+            component.validate().map_err(|error| error.within(component.describe()))?;
+
+            if let Some(columns) = columns {
+                component.check_columns(columns).map_err(|error| error.within(component.describe()))?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn required_packages(&self) -> Vec<Package> {
+        let mut packages: Vec<Package> = self.components
+            .iter()
+            .flat_map(|component| component.required_packages())
+            .collect();
+
+        if self.positioning.contains(FLOAT_HERE_POSITIONING) {
+            packages.push(Package::new(FLOAT_PACKAGE, vec![]));
+        }
+
+        if let Some(column_spec) = &self.column_spec {
+            packages.extend(column_spec.required_packages());
+        }
+
+        if let Some(style) = &self.style {
+            packages.extend(style.required_packages());
+        }
+
+        if let Some(caption) = &self.caption {
+            packages.extend(caption.required_packages());
+        }
+
+        packages
+    }
+
+    fn register_labels(&self, registry: &mut LabelRegistry) -> Result<(), RustexError> {
+        registry.register(&self.get_label())
+    }
+
+    fn check_references(&self, registry: &LabelRegistry) -> Result<(), RustexError> {
+        if let Some(caption) = &self.caption {
+            caption.check_references(registry)?;
+        }
+
+        for component in &self.components {
+            component.check_references(registry).map_err(|error| error.within(component.describe()))?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Container for Table {
@@ -162,8 +353,87 @@ impl Container for Table {
     }
 }
 
+/// Counts the columns of a `tabular` column specification, e.g. 3 for `|l|*{2}{c}|`.
+/// Returns `None` for column types defined by the user, whose width is unknown.
+fn count_columns(options: &str) -> Option<usize> {
+    let characters: Vec<char> = options.chars().collect();
+    let mut count = 0;
+    let mut index = 0;
+
+    while index < characters.len() {
+        match characters[index] {
+            'l' | 'c' | 'r' | 'X' => count += 1,
+            'S' => {
+                count += 1;
+
+                if characters.get(index + 1) == Some(&'[') {
+                    index += characters[index..].iter().position(|character| *character == ']')?;
+                }
+            }
+            'p' | 'm' | 'b' => {
+                count += 1;
+                index = read_group(&characters, index + 1)?.1;
+                continue;
+            }
+            '@' | '!' | '>' | '<' => {
+                index = read_group(&characters, index + 1)?.1;
+                continue;
+            }
+            '*' => {
+                let (repetitions, next_index) = read_group(&characters, index + 1)?;
+                let (repeated_options, next_index) = read_group(&characters, next_index)?;
+                count += repetitions.trim().parse::<usize>().ok()? * count_columns(&repeated_options)?;
+                index = next_index;
+                continue;
+            }
+            character if character == '|' || character.is_whitespace() => {}
+            _ => return None
+        }
+
+        index += 1;
+    }
+
+    Some(count)
+}
+
+// Reads a `{...}` group starting at `index`, and returns its content with the index following it
+fn read_group(characters: &[char], index: usize) -> Option<(String, usize)> {
+    let start = index + characters[index..].iter().take_while(|character| character.is_whitespace()).count();
+
+    if characters.get(start) != Some(&'{') {
+        return None;
+    }
+
+    let mut depth = 0;
+
+    for (offset, character) in characters[start..].iter().enumerate() {
+        match character {
+            '{' => depth += 1,
+            '}' => depth -= 1,
+            _ => {}
+        }
+
+        if depth == 0 {
+            let end = start + offset;
+            return Some((characters[start + 1..end].iter().collect(), end + 1));
+        }
+    }
+
+    None
+}
+
 /// A component that can be added to a `Table`
-pub trait TableComponent: Item {}
+pub trait TableComponent: Item {
+    /// Checks that the `TableComponent` fits a `Table` of `columns` columns.
+    fn check_columns(&self, _columns: usize) -> Result<(), RustexError> {
+        Ok(())
+    }
+
+    /// Returns whether the `TableComponent` is a row, counted in the header of a `Table`.
+    fn is_row(&self) -> bool {
+        false
+    }
+}
 
 /// A horizontal line that can be added to a `Table`
 pub struct HorizontalLine {
@@ -179,50 +449,377 @@ impl HorizontalLine {
     }
 }
 
+impl Default for HorizontalLine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Item for HorizontalLine {
-    fn build(&self, doc: &Document) -> Result<(), Error> {
-        write_indented_line(&doc, &self.indent, DEF_HORIZONTAL_LINE)
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
+        write_indented_line(doc, &self.indent, DEF_HORIZONTAL_LINE)
     }
 
     fn update_indent(&mut self, super_indent: &usize) {
         self.indent= super_indent + 1;
     }
+
+    fn describe(&self) -> String {
+        String::from("HorizontalLine")
+    }
 }
 
 impl TableComponent for HorizontalLine {}
 
+/// Defines the alignment of the content of a `TableCell`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CellAlignment {
+    Left,
+    Center,
+    Right
+}
+
+impl CellAlignment {
+    pub fn get_def(&self) -> &str {
+        match self {
+            CellAlignment::Left => "l",
+            CellAlignment::Center => "c",
+            CellAlignment::Right => "r"
+        }
+    }
+}
+
+/// A cell of a `TableRow`, which can span several columns (`\multicolumn`)
+/// and several rows (`\multirow`).
+///
+/// The cells below a cell spanning several rows are covered by it, so they must be left empty.
+/// A cell spanning several columns, or with its own alignment, replaces the options of its columns:
+/// its vertical rules must then be set again.
+///
+/// Example:
+///
+/// ```rust
+/// # use rustex::*;
+/// let mut group_cell = TableCell::new(Text::new("Group A"));
+/// group_cell.set_row_span(2);
+///
+/// let mut total_cell = TableCell::new(Text::new("**Total**"));
+/// total_cell.set_column_span(2);
+/// total_cell.set_alignment(CellAlignment::Right);
+///
+/// let row_1 = TableRow::from_cells(vec![group_cell, TableCell::new(Text::new("1")), TableCell::new(Text::new("2"))]);
+/// let row_2 = TableRow::from_cells(vec![TableCell::new(Text::new("")), total_cell]);
+/// ```
+///
+/// Generated LaTeX:
+///
+/// ```tex
+/// \multirow{2}{*}{Group A} & 1 & 2 \\
+///  & \multicolumn{2}{r}{\textbf{Total}} \\
+/// ```
+pub struct TableCell {
+    content: Text,
+    column_span: usize,
+    row_span: usize,
+    alignment: Option<CellAlignment>,
+    left_rule: bool,
+    right_rule: bool
+}
+
+impl TableCell {
+    /// Initializes a new `TableCell` object, spanning one column and one row
+    pub fn new(_content: Text) -> Self {
+        Self {
+            content: _content,
+            column_span: 1,
+            row_span: 1,
+            alignment: None,
+            left_rule: false,
+            right_rule: false
+        }
+    }
+
+    pub fn set_column_span(&mut self, column_span: usize) {
+        self.column_span = column_span;
+    }
+
+    /// Sets the number of rows spanned by the `TableCell`. The `multirow` package is added automatically.
+    pub fn set_row_span(&mut self, row_span: usize) {
+        self.row_span = row_span;
+    }
+
+    /// Overrides the alignment of the columns of the `TableCell`.
+    /// Defaults to `CellAlignment::Center` for a cell spanning several columns.
+    pub fn set_alignment(&mut self, alignment: CellAlignment) {
+        self.alignment = Some(alignment);
+    }
+
+    /// Sets the vertical rules on the left and on the right of a cell spanning several columns,
+    /// or with its own alignment.
+    pub fn set_vertical_rules(&mut self, left: bool, right: bool) {
+        self.left_rule = left;
+        self.right_rule = right;
+    }
+
+    fn validate(&self) -> Result<(), RustexError> {
+        if self.column_span == 0 || self.row_span == 0 {
+            return Err(RustexError::validation("a table cell must span at least one column and one row"));
+        }
+
+        self.content.validate_inline()
+    }
+
+    fn render(&self) -> Result<String, RustexError> {
+        let mut cell_str = self.content.render_inline()?;
+
+        if self.row_span > 1 {
+            cell_str = format!("{}{}{}{}", DEF_MULTIROW, into_braces(&self.row_span.to_string()),
+                               into_braces(&String::from(MULTIROW_NATURAL_WIDTH)), into_braces(&cell_str));
+        }
+
+        if self.column_span > 1 || self.alignment.is_some() {
+            let alignment = self.alignment.unwrap_or(CellAlignment::Center);
+            let column_options = format!("{}{}{}",
+                                         if self.left_rule { DEF_VERTICAL_RULE } else { "" },
+                                         alignment.get_def(),
+                                         if self.right_rule { DEF_VERTICAL_RULE } else { "" });
+
+            cell_str = format!("{}{}{}{}", DEF_MULTICOLUMN, into_braces(&self.column_span.to_string()),
+                               into_braces(&column_options), into_braces(&cell_str));
+        }
+
+        Ok(cell_str)
+    }
+
+    fn required_packages(&self) -> Vec<Package> {
+        let mut packages = self.content.required_packages();
+
+        if self.row_span > 1 {
+            packages.push(Package::new(MULTIROW_PACKAGE, vec![]));
+        }
+
+        packages
+    }
+}
+
+impl From<Text> for TableCell {
+    fn from(content: Text) -> Self {
+        TableCell::new(content)
+    }
+}
+
 /// A row that can be added to a `Table`
 pub struct TableRow {
-    content: Vec<Text>,
+    cells: Vec<TableCell>,
     indent: usize
 }
 
 impl TableRow {
-    /// Initializes a new `TableRow` object
+    /// Initializes a new `TableRow` object, with one `Text` per column
     pub fn new(_content: Vec<Text>) -> Self {
+        Self::from_cells(_content.into_iter().map(TableCell::from).collect())
+    }
+
+    /// Initializes a new `TableRow` object from `TableCells`, which can span several columns and rows
+    pub fn from_cells(_cells: Vec<TableCell>) -> Self {
         Self {
-            content: _content,
+            cells: _cells,
             indent: 0
         }
     }
+
+    fn get_column_count(&self) -> usize {
+        self.cells.iter().map(|cell| cell.column_span).sum()
+    }
 }
 
 impl Item for TableRow {
-    fn build(&self, doc: &Document) -> Result<(), Error> {
+    fn build(&self, doc: &Document) -> Result<(), RustexError> {
         let mut cells: Vec<String> = Vec::new();
 
-        for cell in &self.content {
-            cells.push(cell.get_string())
+        for cell in &self.cells {
+            cells.push(cell.render()?)
         }
 
         let mut formatted_row: String = cells.join(" & ");
         formatted_row = format!("{} \\\\", formatted_row);
-        write_indented_line(&doc, &self.indent, &formatted_row)
+        write_indented_line(doc, &self.indent, &formatted_row)
     }
 
     fn update_indent(&mut self, super_indent: &usize) {
         self.indent= super_indent + 1;
     }
+
+    fn describe(&self) -> String {
+        String::from("TableRow")
+    }
+
+    fn validate(&self) -> Result<(), RustexError> {
+        for cell in &self.cells {
+            cell.validate()?;
+        }
+
+        Ok(())
+    }
+
+    fn required_packages(&self) -> Vec<Package> {
+        self.cells
+            .iter()
+            .flat_map(|cell| cell.required_packages())
+            .collect()
+    }
+
+    fn check_references(&self, registry: &LabelRegistry) -> Result<(), RustexError> {
+        for cell in &self.cells {
+            cell.content.check_references(registry)?;
+        }
+
+        Ok(())
+    }
 }
 
-impl TableComponent for TableRow {}
\ No newline at end of file
+impl TableComponent for TableRow {
+    fn check_columns(&self, columns: usize) -> Result<(), RustexError> {
+        let row_columns = self.get_column_count();
+
+        match row_columns == columns {
+            true => Ok(()),
+            false => Err(RustexError::validation(&format!(
+                "row spans {} column(s) but the table has {} column(s)", row_columns, columns
+            )))
+        }
+    }
+
+    fn is_row(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cell::RefCell;
+    use std::io::{self, Write};
+    use std::rc::Rc;
+    use super::*;
+    use crate::components::container::*;
+
+    #[derive(Clone, Default)]
+    struct SharedOutput {
+        bytes: Rc<RefCell<Vec<u8>>>
+    }
+
+    impl Write for SharedOutput {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.bytes.borrow_mut().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    // Builds the `Table` in a `Section`, and returns the error with everything written before it
+    fn build_error(table: Table) -> (RustexError, String) {
+        let output = SharedOutput::default();
+        let mut doc = Document::from_writer(output.clone(), DocumentClass::new(ClassType::Article, vec![]));
+        let mut section = Section::new("Data", SectionType::Section, true, "sec_data");
+        section.add_item(Text::new("Before the table"));
+        section.add_item(table);
+        doc.add_item(section);
+
+        let error = doc.build().unwrap_err();
+        let written = String::from_utf8(output.bytes.take()).unwrap();
+        (error, written)
+    }
+
+    fn row(cells: &[&str]) -> TableRow {
+        TableRow::new(cells.iter().map(|cell| Text::new(cell)).collect())
+    }
+
+    #[test]
+    fn rejects_rows_spanning_other_column_counts() {
+        let mut table = Table::new("H", "|l|c|r|", false, None, "tab_1");
+        table.add_component(row(&["a", "b", "c"]));
+        table.add_component(HorizontalLine::new());
+        table.add_component(row(&["d", "e"]));
+
+        let (error, written) = build_error(table);
+
+        assert_eq!(error.to_string(),
+                   "Section 'Data' > Table 'tab_1' > TableRow: validation error: row spans 2 column(s) but the table has 3 column(s)");
+        assert!(written.is_empty(), "partial output:\n{}", written);
+    }
+
+    #[test]
+    fn counts_cells_spanning_several_columns() {
+        let mut wide_cell = TableCell::new(Text::new("wide"));
+        wide_cell.set_column_span(2);
+
+        let mut table = Table::from_columns("H", ColumnSpec::new().left().repeat(2, ColumnSpec::new().center()), false, None, "tab_1");
+        table.add_component(TableRow::from_cells(vec![TableCell::new(Text::new("a")), wide_cell]));
+        assert!(table.validate().is_ok());
+
+        let mut wide_cell = TableCell::new(Text::new("wide"));
+        wide_cell.set_column_span(3);
+        table.add_component(TableRow::from_cells(vec![TableCell::new(Text::new("a")), wide_cell]));
+
+        let (error, _) = build_error(table);
+        assert!(error.to_string().ends_with("row spans 4 column(s) but the table has 3 column(s)"));
+    }
+
+    #[test]
+    fn rejects_paragraph_breaks_in_cells() {
+        let mut table = Table::new("H", "|l|l|", false, None, "tab_1");
+        table.add_component(row(&["a", "first paragraph\n\n    second paragraph"]));
+
+        let (error, written) = build_error(table);
+
+        assert_eq!(error.to_string(),
+                   "Section 'Data' > Table 'tab_1' > TableRow: validation error: \
+                    a paragraph break cannot be written in a caption, a table cell or a list label");
+        assert!(written.is_empty(), "partial output:\n{}", written);
+    }
+
+    #[test]
+    fn writes_multi_line_cells_on_one_line() {
+        let cell = TableCell::new(Text::new("first line\n        second line"));
+        assert_eq!(cell.render().unwrap(), "first line second line");
+    }
+
+    #[test]
+    fn rejects_cells_without_span() {
+        let mut cell = TableCell::new(Text::new("a"));
+        cell.set_row_span(0);
+
+        let mut table = Table::new("H", "l", false, None, "tab_1");
+        table.add_component(TableRow::from_cells(vec![cell]));
+
+        let (error, written) = build_error(table);
+
+        assert!(error.to_string().ends_with("a table cell must span at least one column and one row"));
+        assert!(written.is_empty());
+    }
+
+    #[test]
+    fn rejects_invalid_column_specs() {
+        let mut table = Table::from_columns("H", ColumnSpec::new().before(r"\bfseries"), false, None, "tab_1");
+        table.add_component(row(&[]));
+
+        let (error, written) = build_error(table);
+
+        assert!(error.to_string().ends_with(r"'>{\bfseries}' is not followed by a column"));
+        assert!(written.is_empty());
+    }
+
+    #[test]
+    fn skips_width_checks_for_unknown_column_types() {
+        let mut table = Table::new("H", "|L|c|", false, None, "tab_1");
+        table.add_component(row(&["a", "b", "c"]));
+
+        assert!(table.validate().is_ok());
+        assert_eq!(count_columns("|l|*{2}{c}|p{3cm}|"), Some(4));
+        assert_eq!(count_columns("@{}S[table-format=3.2]>{\\bfseries}l@{}"), Some(2));
+        assert_eq!(count_columns("|L|c|"), None);
+    }
+}
@@ -0,0 +1,563 @@
+use std::fmt;
+use crate::error::PATH_SEPARATOR;
+
+// TeX wraps the lines of its log at this length
+const LOG_LINE_LENGTH: usize = 79;
+
+const ERROR_PREFIX: &str = "! ";
+const ERROR_LINE_PREFIX: &str = "l.";
+const OVERFULL_PREFIX: &str = "Overfull ";
+const UNDERFULL_PREFIX: &str = "Underfull ";
+const LINES_MARKER: &str = "at lines ";
+const LINE_MARKER: &str = "at line ";
+const INPUT_LINE_MARKER: &str = "on input line ";
+const REFERENCE_WARNING_PREFIX: &str = "LaTeX Warning: Reference `";
+const CITATION_WARNING_PREFIX: &str = "LaTeX Warning: Citation `";
+
+/// The lines of the generated LaTeX file written by an `Item`.
+#[derive(Clone, Debug)]
+pub struct SourceMapEntry {
+    path: Vec<String>,
+    start_line: usize,
+    end_line: usize
+}
+
+impl SourceMapEntry {
+    /// Gets the path of the `Item`, e.g. `Chapter 'Results' > Section 'Data' > Table 'tab_1'`.
+    pub fn get_path(&self) -> String {
+        self.path.join(PATH_SEPARATOR)
+    }
+
+    /// Gets the descriptions of the `Item` and its enclosing `Items`, outermost first.
+    pub fn get_path_segments(&self) -> &[String] {
+        &self.path
+    }
+
+    /// Gets the first line (starting at 1) written by the `Item`.
+    pub fn get_start_line(&self) -> usize {
+        self.start_line
+    }
+
+    /// Gets the last line written by the `Item`.
+    pub fn get_end_line(&self) -> usize {
+        self.end_line
+    }
+}
+
+/// Maps the lines of a generated LaTeX file to the `Items` that wrote them.
+/// Recorded by the `Document` while it is built.
+#[derive(Clone, Debug, Default)]
+pub struct SourceMap {
+    entries: Vec<SourceMapEntry>
+}
+
+impl SourceMap {
+    /// Gets every recorded entry, in the order their `Items` were built.
+    pub fn get_entries(&self) -> &[SourceMapEntry] {
+        &self.entries
+    }
+
+    /// Finds the innermost `Item` that wrote a line of the LaTeX file.
+    pub fn find(&self, line: usize) -> Option<&SourceMapEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.start_line <= line && line <= entry.end_line)
+            .min_by_key(|entry| (entry.end_line - entry.start_line, usize::MAX - entry.path.len()))
+    }
+
+    pub(crate) fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    pub(crate) fn record(&mut self, path: Vec<String>, start_line: usize, end_line: usize) {
+        if end_line >= start_line {
+            self.entries.push(SourceMapEntry { path, start_line, end_line });
+        }
+    }
+}
+
+/// The type of a `Diagnostic` found in the log of a LaTeX engine.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DiagnosticKind {
+    /// A TeX error, e.g. `! Undefined control sequence.`
+    Error,
+    /// An overfull `\hbox` or `\vbox`.
+    OverfullBox,
+    /// An underfull `\hbox` or `\vbox`.
+    UnderfullBox,
+    /// A reference to an undefined label.
+    UndefinedReference(String),
+    /// A citation of an undefined bibliography entry.
+    UndefinedCitation(String)
+}
+
+/// An error or a warning found in the log of a LaTeX engine.
+#[derive(Clone, Debug)]
+pub struct Diagnostic {
+    kind: DiagnosticKind,
+    message: String,
+    line: Option<usize>,
+    file: Option<String>,
+    item: Option<SourceMapEntry>
+}
+
+impl Diagnostic {
+    pub fn get_kind(&self) -> &DiagnosticKind {
+        &self.kind
+    }
+
+    pub fn get_message(&self) -> &str {
+        &self.message
+    }
+
+    /// Gets the line of the LaTeX file the `Diagnostic` refers to, if any.
+    pub fn get_line(&self) -> Option<usize> {
+        self.line
+    }
+
+    /// Gets the file being read when the `Diagnostic` was logged, as written in the log (`./main.tex`), if any.
+    pub fn get_file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// Gets the `Item` that wrote the line of the `Diagnostic`, if any.
+    pub fn get_item(&self) -> Option<&SourceMapEntry> {
+        self.item.as_ref()
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.kind == DiagnosticKind::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(item) = &self.item {
+            write!(f, "{}: ", item.get_path())?;
+        }
+
+        match self.line {
+            Some(line) => write!(f, "{} (line {})", self.message, line),
+            None => write!(f, "{}", self.message)
+        }
+    }
+}
+
+/// Parses the log of a LaTeX engine into `Diagnostics` pointing at the `Items` of the `SourceMap`.
+/// Only the lines of the main LaTeX file, the first file opened in the log, are mapped to `Items`:
+/// a `Diagnostic` raised in an `\input` file or a package has no `Item`.
+///
+/// Example:
+///
+/// ```rust
+/// # use rustex::*;
+/// let doc_class = DocumentClass::new(ClassType::Article, vec![]);
+/// let mut doc = Document::from_writer(Vec::new(), doc_class);
+/// let mut section = Section::new("Data", SectionType::Section, true, "sec_1");
+/// section.add_item(Text::raw(r"\undefinedmacro"));
+/// doc.add_item(section);
+///
+/// let latex = doc.render_to_string().unwrap();
+/// let line = latex.lines().position(|line| line.contains(r"\undefinedmacro")).unwrap() + 1;
+/// let log = format!("! Undefined control sequence.\nl.{} \\undefinedmacro\n", line);
+///
+/// let diagnostics = parse_log(&log, &doc.get_source_map());
+/// assert_eq!(diagnostics[0].get_item().unwrap().get_path(), "Section 'Data' > Text");
+/// ```
+pub fn parse_log(log: &str, source_map: &SourceMap) -> Vec<Diagnostic> {
+    let lines = unwrap_log_lines(log);
+    let mut diagnostics: Vec<Diagnostic> = Vec::new();
+    let mut files = FileStack::default();
+    let mut skip_files = SkipFiles::None;
+
+    for (index, line) in lines.iter().enumerate() {
+        if let Some(mut diagnostic) = parse_log_line(line, &lines[index + 1..]) {
+            diagnostic.file = files.current().map(String::from);
+
+            if files.in_main_file() {
+                diagnostic.item = diagnostic.line
+                    .and_then(|line| source_map.find(line))
+                    .cloned();
+            }
+
+            diagnostics.push(diagnostic);
+        }
+
+        skip_files = match skip_files {
+            SkipFiles::None => files.update(line),
+            SkipFiles::Line => SkipFiles::None,
+            SkipFiles::UntilBlankLine if line.trim().is_empty() => SkipFiles::None,
+            SkipFiles::UntilBlankLine => SkipFiles::UntilBlankLine
+        };
+    }
+
+    diagnostics
+}
+
+// Lines quoting the LaTeX source or the content of a box may hold unbalanced parentheses,
+// so they are not read for the files opened and closed in the log
+enum SkipFiles {
+    None,
+    Line,
+    UntilBlankLine
+}
+
+// TeX logs `(<file>` when it opens a file and `)` when it closes it
+#[derive(Default)]
+struct FileStack {
+    // Parentheses that do not open a file are pushed as `None`, to be popped by their `)`
+    files: Vec<Option<String>>,
+    main_file: Option<String>
+}
+
+impl FileStack {
+    fn current(&self) -> Option<&str> {
+        self.files.iter().rev().find_map(|file| file.as_deref())
+    }
+
+    // Logs without any opened file are considered to come from the main file
+    fn in_main_file(&self) -> bool {
+        match self.current() {
+            Some(file) => self.main_file.as_deref() == Some(file),
+            None => true
+        }
+    }
+
+    // Reads the parentheses of a line, and returns the lines to skip after it
+    fn update(&mut self, line: &str) -> SkipFiles {
+        if line.starts_with(OVERFULL_PREFIX) || line.starts_with(UNDERFULL_PREFIX) {
+            return SkipFiles::UntilBlankLine;
+        }
+
+        if line.strip_prefix(ERROR_LINE_PREFIX).and_then(parse_leading_number).is_some() {
+            return SkipFiles::Line;
+        }
+
+        for (index, character) in line.char_indices() {
+            match character {
+                '(' => {
+                    let file = parse_file_name(&line[index + 1..]);
+
+                    if self.main_file.is_none() {
+                        self.main_file = file.clone();
+                    }
+
+                    self.files.push(file);
+                }
+                ')' => {
+                    self.files.pop();
+                }
+                _ => {}
+            }
+        }
+
+        SkipFiles::None
+    }
+}
+
+// e.g. `./main.tex` or `/usr/share/texlive/texmf-dist/tex/latex/base/article.cls`, but not `12.3pt too wide`
+fn parse_file_name(text: &str) -> Option<String> {
+    let name: String = text
+        .chars()
+        .take_while(|character| !character.is_whitespace() && *character != '(' && *character != ')')
+        .collect();
+    let (stem, extension) = name.rsplit_once('.')?;
+
+    match !stem.is_empty() && !extension.is_empty() && extension.chars().all(|character| character.is_ascii_alphabetic()) {
+        true => Some(name),
+        false => None
+    }
+}
+
+fn parse_log_line(line: &str, following_lines: &[String]) -> Option<Diagnostic> {
+    if let Some(message) = line.strip_prefix(ERROR_PREFIX) {
+        return Some(parse_error(message, following_lines));
+    }
+
+    if line.starts_with(OVERFULL_PREFIX) {
+        return Some(parse_box_warning(line, DiagnosticKind::OverfullBox));
+    }
+
+    if line.starts_with(UNDERFULL_PREFIX) {
+        return Some(parse_box_warning(line, DiagnosticKind::UnderfullBox));
+    }
+
+    if let Some(rest) = line.strip_prefix(REFERENCE_WARNING_PREFIX) {
+        return Some(parse_reference_warning(line, rest, DiagnosticKind::UndefinedReference));
+    }
+
+    line.strip_prefix(CITATION_WARNING_PREFIX)
+        .map(|rest| parse_reference_warning(line, rest, DiagnosticKind::UndefinedCitation))
+}
+
+// The error message is followed by the context of the error, e.g. `l.42 \foo`
+fn parse_error(message: &str, following_lines: &[String]) -> Diagnostic {
+    let line = following_lines
+        .iter()
+        .take_while(|line| !line.starts_with(ERROR_PREFIX))
+        .find_map(|line| {
+            let rest = line.strip_prefix(ERROR_LINE_PREFIX)?;
+            parse_leading_number(rest)
+        });
+
+    Diagnostic {
+        kind: DiagnosticKind::Error,
+        message: String::from(message.trim()),
+        line,
+        file: None,
+        item: None
+    }
+}
+
+// e.g. `Overfull \hbox (12.3pt too wide) in paragraph at lines 10--12`
+fn parse_box_warning(line: &str, kind: DiagnosticKind) -> Diagnostic {
+    let line_number = [LINES_MARKER, LINE_MARKER]
+        .iter()
+        .find_map(|marker| {
+            let (_, rest) = line.split_once(marker)?;
+            parse_leading_number(rest)
+        });
+
+    Diagnostic {
+        kind,
+        message: String::from(line.trim()),
+        line: line_number,
+        file: None,
+        item: None
+    }
+}
+
+// e.g. LaTeX Warning: Reference `fig_1' on page 1 undefined on input line 25.
+fn parse_reference_warning(line: &str, rest: &str, kind: fn(String) -> DiagnosticKind) -> Diagnostic {
+    let name = rest.split('\'').next().unwrap_or_default();
+    let line_number = line
+        .split_once(INPUT_LINE_MARKER)
+        .and_then(|(_, rest)| parse_leading_number(rest));
+
+    Diagnostic {
+        kind: kind(String::from(name)),
+        message: String::from(line.trim()),
+        line: line_number,
+        file: None,
+        item: None
+    }
+}
+
+fn parse_leading_number(text: &str) -> Option<usize> {
+    let digits: String = text
+        .chars()
+        .take_while(|character| character.is_ascii_digit())
+        .collect();
+
+    digits.parse().ok()
+}
+
+// Joins the lines that TeX wrapped at `LOG_LINE_LENGTH` characters
+fn unwrap_log_lines(log: &str) -> Vec<String> {
+    let mut lines: Vec<String> = Vec::new();
+    let mut current_line = String::new();
+
+    for line in log.lines() {
+        current_line.push_str(line);
+
+        if line.chars().count() != LOG_LINE_LENGTH {
+            lines.push(std::mem::take(&mut current_line));
+        }
+    }
+
+    if !current_line.is_empty() {
+        lines.push(current_line);
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Modelled on a pdflatex log
+    const PDFLATEX_LOG: &str = r"This is pdfTeX, Version 3.141592653-2.6-1.40.25 (TeX Live 2023) (preloaded format=pdflatex 2023.4.1)
+ entering extended mode
+(./main.tex
+LaTeX2e <2022-11-01> patch level 1
+(/usr/share/texlive/texmf-dist/tex/latex/base/article.cls
+Document Class: article 2022/07/02 v1.4n Standard LaTeX document class
+)
+Overfull \hbox (12.34567pt too wide) in paragraph at lines 14--16
+[]\OT1/cmr/m/n/10 Some very long text that does not fit the line|
+ []
+
+
+LaTeX Warning: Reference `sec_a_very_long_label_name_for_the_results_section' o
+n page 3 undefined on input line 57.
+
+! Undefined control sequence.
+l.61     \undefinedmacro
+                        {x}
+The control sequence at the end of the top line
+of your error message was never \def'ed.
+
+Underfull \hbox (badness 10000) in paragraph at lines 70--71
+
+ []
+
+";
+
+    // Modelled on a xelatex log
+    const XELATEX_LOG: &str = r"This is XeTeX, Version 3.141592653-2.6-0.999995 (TeX Live 2023) (preloaded format=xelatex 2023.4.1)
+ entering extended mode
+(./main.tex
+LaTeX2e <2022-11-01> patch level 1
+
+! LaTeX Error: File `a_really_long_package_name_that_is_not_installed_here.sty'
+ not found.
+
+Type X to quit or <RETURN> to proceed,
+or enter new name. (Default extension: sty)
+
+Enter file name:
+! Emergency stop.
+<read *>
+
+l.3 \usepackage
+
+LaTeX Warning: Citation `knuth84' on page 2 undefined on input line 41.
+
+Overfull \hbox (1.2pt too wide) detected at line 50
+[]
+
+Overfull \vbox (3.0pt too high) has occurred while \output is active []
+
+";
+
+    fn source_map(entries: &[(&[&str], usize, usize)]) -> SourceMap {
+        let mut source_map = SourceMap::default();
+
+        for (path, start_line, end_line) in entries {
+            let path = path.iter().map(|segment| String::from(*segment)).collect();
+            source_map.record(path, *start_line, *end_line);
+        }
+
+        source_map
+    }
+
+    #[test]
+    fn unwraps_log_lines() {
+        let lines = unwrap_log_lines(PDFLATEX_LOG);
+
+        assert!(lines.contains(&String::from(
+            "LaTeX Warning: Reference `sec_a_very_long_label_name_for_the_results_section' on page 3 undefined on input line 57."
+        )));
+        assert!(lines.iter().all(|line| !line.starts_with("n page 3")));
+        assert_eq!(unwrap_log_lines(&format!("{}\nend", "x".repeat(LOG_LINE_LENGTH))), vec![format!("{}end", "x".repeat(LOG_LINE_LENGTH))]);
+        assert_eq!(unwrap_log_lines(&"y".repeat(LOG_LINE_LENGTH)), vec!["y".repeat(LOG_LINE_LENGTH)]);
+    }
+
+    #[test]
+    fn parses_pdflatex_log() {
+        let diagnostics = parse_log(PDFLATEX_LOG, &SourceMap::default());
+        let kinds: Vec<&DiagnosticKind> = diagnostics.iter().map(|diagnostic| diagnostic.get_kind()).collect();
+
+        assert_eq!(kinds, vec![
+            &DiagnosticKind::OverfullBox,
+            &DiagnosticKind::UndefinedReference(String::from("sec_a_very_long_label_name_for_the_results_section")),
+            &DiagnosticKind::Error,
+            &DiagnosticKind::UnderfullBox
+        ]);
+
+        let lines: Vec<Option<usize>> = diagnostics.iter().map(|diagnostic| diagnostic.get_line()).collect();
+        assert_eq!(lines, vec![Some(14), Some(57), Some(61), Some(70)]);
+
+        assert_eq!(diagnostics[2].get_message(), "Undefined control sequence.");
+        assert!(diagnostics[2].is_error());
+        assert!(!diagnostics[0].is_error());
+    }
+
+    #[test]
+    fn parses_xelatex_log() {
+        let diagnostics = parse_log(XELATEX_LOG, &SourceMap::default());
+        let kinds: Vec<&DiagnosticKind> = diagnostics.iter().map(|diagnostic| diagnostic.get_kind()).collect();
+
+        assert_eq!(kinds, vec![
+            &DiagnosticKind::Error,
+            &DiagnosticKind::Error,
+            &DiagnosticKind::UndefinedCitation(String::from("knuth84")),
+            &DiagnosticKind::OverfullBox,
+            &DiagnosticKind::OverfullBox
+        ]);
+
+        // The first error is followed by another error before any `l.<n>` line
+        assert_eq!(diagnostics[0].get_message(),
+                   "LaTeX Error: File `a_really_long_package_name_that_is_not_installed_here.sty' not found.");
+        assert_eq!(diagnostics[0].get_line(), None);
+        assert_eq!(diagnostics[1].get_message(), "Emergency stop.");
+        assert_eq!(diagnostics[1].get_line(), Some(3));
+
+        assert_eq!(diagnostics[2].get_line(), Some(41));
+        assert_eq!(diagnostics[3].get_line(), Some(50));
+        assert_eq!(diagnostics[4].get_line(), None);
+    }
+
+    #[test]
+    fn maps_diagnostics_to_items() {
+        let source_map = source_map(&[
+            (&["Package 'xcolor'"], 3, 3),
+            (&["Chapter 'Results'"], 50, 80),
+            (&["Chapter 'Results'", "Section 'Data'"], 55, 65),
+            (&["Chapter 'Results'", "Section 'Data'", "Text"], 61, 61)
+        ]);
+
+        let diagnostics = parse_log(PDFLATEX_LOG, &source_map);
+        let paths: Vec<Option<String>> = diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.get_item().map(|item| item.get_path()))
+            .collect();
+
+        assert_eq!(paths, vec![
+            None,
+            Some(String::from("Chapter 'Results' > Section 'Data'")),
+            Some(String::from("Chapter 'Results' > Section 'Data' > Text")),
+            Some(String::from("Chapter 'Results'"))
+        ]);
+
+        assert_eq!(diagnostics[2].to_string(),
+                   "Chapter 'Results' > Section 'Data' > Text: Undefined control sequence. (line 61)");
+    }
+
+    #[test]
+    fn maps_only_the_lines_of_the_main_file() {
+        let log = r"(./main.tex (/usr/share/texlive/texmf-dist/tex/latex/base/article.cls
+Document Class: article 2022/07/02 v1.4n Standard LaTeX document class
+) (./chapter.tex
+! Undefined control sequence.
+l.4 f(x
+       \foo
+)
+Overfull \hbox (1.2pt too wide) in paragraph at lines 4--5
+[]\OT1/cmr/m/n/10 f(x|
+
+! Undefined control sequence.
+l.4 \bar
+) ";
+        let source_map = source_map(&[(&["Text"], 4, 5)]);
+
+        let diagnostics = parse_log(log, &source_map);
+        let files: Vec<Option<&str>> = diagnostics.iter().map(|diagnostic| diagnostic.get_file()).collect();
+        let paths: Vec<Option<String>> = diagnostics
+            .iter()
+            .map(|diagnostic| diagnostic.get_item().map(|item| item.get_path()))
+            .collect();
+
+        assert_eq!(files, vec![Some("./chapter.tex"), Some("./main.tex"), Some("./main.tex")]);
+        assert_eq!(paths, vec![None, Some(String::from("Text")), Some(String::from("Text"))]);
+    }
+
+    #[test]
+    fn ignores_logs_without_diagnostics() {
+        let log = "This is pdfTeX, Version 3.141592653\n(./main.tex)\nOutput written on main.pdf (1 page, 1234 bytes).\n";
+
+        assert!(parse_log(log, &SourceMap::default()).is_empty());
+        assert!(parse_log("", &SourceMap::default()).is_empty());
+    }
+}
@@ -0,0 +1,121 @@
+use std::fmt;
+use std::io;
+
+// Separates the `Items` in the path of a `RustexError` or a `Diagnostic`
+pub(crate) const PATH_SEPARATOR: &str = " > ";
+
+/// The error returned when a `Document` cannot be built or compiled.
+/// Every variant carries the path of the `Item` that failed,
+/// e.g. `Chapter 'Results' > Section 'Data' > Table 'tab_1'`.
+#[derive(Debug)]
+pub enum RustexError {
+    /// The LaTeX output or an asset could not be written.
+    Io {
+        path: Vec<String>,
+        source: io::Error
+    },
+    /// The `Document` is invalid (duplicate labels, mismatched table widths, etc.).
+    Validation {
+        path: Vec<String>,
+        message: String
+    },
+    /// A text could not be formatted.
+    Format {
+        path: Vec<String>,
+        message: String
+    },
+    /// The LaTeX engine could not be found or failed to compile the document.
+    /// `log` contains the log of the engine, if any.
+    Compile {
+        path: Vec<String>,
+        message: String,
+        log: String
+    }
+}
+
+impl RustexError {
+    /// Initializes a new validation error.
+    pub fn validation(message: &str) -> Self {
+        RustexError::Validation {
+            path: Vec::new(),
+            message: String::from(message)
+        }
+    }
+
+    /// Initializes a new formatting error.
+    pub fn format(message: &str) -> Self {
+        RustexError::Format {
+            path: Vec::new(),
+            message: String::from(message)
+        }
+    }
+
+    /// Initializes a new compilation error.
+    pub fn compile(message: &str, log: &str) -> Self {
+        RustexError::Compile {
+            path: Vec::new(),
+            message: String::from(message),
+            log: String::from(log)
+        }
+    }
+
+    /// Prepends the description of the enclosing `Item` to the path of the error.
+    pub fn within(mut self, item_description: String) -> Self {
+        self.path_mut().insert(0, item_description);
+        self
+    }
+
+    /// Gets the path of the `Item` that failed, e.g. `Chapter 'Results' > Section 'Data'`.
+    pub fn path(&self) -> String {
+        match self {
+            RustexError::Io { path, .. } => path.join(PATH_SEPARATOR),
+            RustexError::Validation { path, .. } => path.join(PATH_SEPARATOR),
+            RustexError::Format { path, .. } => path.join(PATH_SEPARATOR),
+            RustexError::Compile { path, .. } => path.join(PATH_SEPARATOR)
+        }
+    }
+
+    fn path_mut(&mut self) -> &mut Vec<String> {
+        match self {
+            RustexError::Io { path, .. } => path,
+            RustexError::Validation { path, .. } => path,
+            RustexError::Format { path, .. } => path,
+            RustexError::Compile { path, .. } => path
+        }
+    }
+}
+
+impl fmt::Display for RustexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = self.path();
+
+        if !path.is_empty() {
+            write!(f, "{}: ", path)?;
+        }
+
+        match self {
+            RustexError::Io { source, .. } => write!(f, "I/O error: {}", source),
+            RustexError::Validation { message, .. } => write!(f, "validation error: {}", message),
+            RustexError::Format { message, .. } => write!(f, "formatting error: {}", message),
+            RustexError::Compile { message, .. } => write!(f, "compilation error: {}", message)
+        }
+    }
+}
+
+impl std::error::Error for RustexError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            RustexError::Io { source, .. } => Some(source),
+            _ => None
+        }
+    }
+}
+
+impl From<io::Error> for RustexError {
+    fn from(error: io::Error) -> Self {
+        RustexError::Io {
+            path: Vec::new(),
+            source: error
+        }
+    }
+}
@@ -24,17 +24,47 @@
 //! - Chapter
 //! - Section
 //! - Enumerate
-//! - Table
-//! - Figure
+//! - Itemize
+//! - Description
+//! - Table, with cells spanning several columns and rows (TableCell)
+//! - Typed column specification of a Table (ColumnSpec), checked against every row
+//! - Table rules (booktabs TopRule, MidRule, BottomRule, PartialRule, LineSpace and PartialLine) and table style presets
+//! - Tables read from CSV files (header detection, column selection and renaming, number formatting)
+//! - Tables built from Rust structs with `#[derive(LatexTable)]` (`derive` feature)
+//! - Figure and SubFigure, with a builder for typed placement and graphics options
 //! - Text
 //! - Label
+//! - References (Ref, PageRef, EqRef, AutoRef)
+//! - Equation, Align and Gather
+//! - Math expressions (MathExpr, built with the functions of `rustex::math`)
+//! - CodeListing (listings or minted)
+//! - Verbatim
 //! - PageBreak
+//! - TableOfContents, ListOfFigures and ListOfTables (depth, title, page break)
+//! - Title page metadata (title, subtitle, authors, date, keywords, abstract) for every class, also set as PDF metadata
+//!
+//! ### _Packages_
+//!
+//! - Automatic package dependency resolution
+//!
+//! ### _Compilation_
+//!
+//! - PDF compilation with a local engine (pdflatex, xelatex, lualatex, latexmk or tectonic)
+//! - Compilation errors and warnings mapped back to the `Items` that produced them
+//! - Referenced images copied or hard-linked beside the LaTeX file, de-duplicated by content
 //!
 //! ### _Formatting_
 //!
 //! - Markdown **bold**
 //! - Markdown _italic_
 //! - Color text
+//! - Inline math
+//! - Inline code
+//! - Paragraphs and line breaks
+//! - Configurable dedent of multi-line strings
+//! - Nested formatting
+//! - Backslash escapes for formatting markers
+//! - Automatic escaping of LaTeX special characters
 //!
 //! ## Example
 //!
@@ -91,7 +121,7 @@
 //! doc.add_packages(packages);
 //! ```
 //!
-//! 3. Add some global `Commands`
+//! 3. Set the title page metadata and add some global `Commands`
 //!
 //! ```
 //! # const DOCUMENT_NAME: &str = "main.tex";
@@ -105,10 +135,11 @@
 //! # let mut doc: Document = Document::new(doc_file, doc_class);
 //! # use rustex::*;
 //! # use std::fs::File;
+//! doc.set_title("Title");
+//! doc.add_authors(vec![Author::new("Author", "Affiliation", "author@example.com")]);
+//! doc.set_date(DocumentDate::Custom(String::from("YYYY / MM / DD")));
+//!
 //! let commands = vec![
-//!     Command::new(r"\title{Title}"),
-//!     Command::new(r"\author{Author}"),
-//!     Command::new(r"\date{YYYY / MM / DD}"),
 //!     Command::new(r"\setlength{\tabcolsep}{18pt}")
 //! ];
 //!
@@ -152,11 +183,42 @@
 //!     doc.add_item(section_1);
 //!     ```
 
+mod compile;
 mod components;
+mod diagnostic;
+mod error;
 #[doc(hidden)]
 mod utilities;
 
 pub use components::document::*;
+pub use components::package::*;
 pub use components::item::*;
 pub use components::container::*;
 pub use components::table::*;
+pub use components::reference::*;
+pub use components::math::*;
+pub use components::expression::{MathExpr, MatrixStyle};
+pub use components::listing::*;
+pub use components::graphics::*;
+pub use components::asset::*;
+pub use components::metadata::*;
+pub use components::contents::*;
+pub use components::columns::*;
+pub use components::rules::*;
+pub use components::csv::*;
+pub use components::records::*;
+pub use compile::*;
+pub use diagnostic::*;
+pub use error::*;
+
+#[cfg(feature = "derive")]
+pub use rustex_derive::LatexTable;
+
+/// Functions to build a `MathExpr`, e.g. `math::frac(1, "n")`.
+/// Import them with `use rustex::math::*` to write formulas concisely.
+pub mod math {
+    pub use crate::components::expression::{
+        sym, num, infinity, paren, func, frac, sqrt, root, sub, sup, sum, prod, integral, matrix, times, plus_minus,
+        equals, not_equals, less_than, less_or_equal, greater_than, greater_or_equal, approx
+    };
+}
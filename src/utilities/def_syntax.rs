@@ -8,23 +8,72 @@ pub const DEF_DOCUMENT_CLASS: &str = r"\documentclass";
 // Package
 pub const DEF_PACKAGE: &str = r"\usepackage";
 pub const USER_PACKAGES_COMMENT: &str = r"% Added packages";
-pub const DEFAULT_PACKAGES_COMMENT: &str = r"% Default packages";
-pub const DEFAULT_FLOAT_PACKAGE: &str = r"\usepackage{float}";
-pub const DEFAULT_GRAPHICX_PACKAGE: &str = r"\usepackage{graphicx}";
-pub const DEFAULT_XCOLOR_PACKAGE: &str = r"\usepackage{xcolor}";
+pub const REQUIRED_PACKAGES_COMMENT: &str = r"% Required packages";
+pub const LATE_PACKAGES_COMMENT: &str = r"% Packages loaded last";
+pub const FLOAT_PACKAGE: &str = "float";
+pub const GRAPHICX_PACKAGE: &str = "graphicx";
+pub const XCOLOR_PACKAGE: &str = "xcolor";
+pub const FLOAT_HERE_POSITIONING: &str = "H";
 
 // Command
 pub const USER_GLOBAL_COMMANDS_COMMENT: &str = r"% Added global commands";
 
-// Enumerate
+// Title page
+pub const TITLE_COMMENT: &str = r"% Title page";
+pub const DEF_TITLE: &str = r"\title";
+pub const DEF_SUBTITLE: &str = r"\subtitle";
+pub const DEF_SUBTITLE_SIZE: &str = r"\large";
+pub const DEF_AUTHOR: &str = r"\author";
+pub const DEF_AUTHOR_SEPARATOR: &str = r"\and";
+pub const DEF_DATE: &str = r"\date";
+pub const DEF_TODAY: &str = r"\today";
+pub const DEF_MAKE_TITLE: &str = r"\maketitle";
+pub const DEF_TITLE_PAGE: &str = r"\titlepage";
+pub const DEF_SIGNATURE: &str = r"\signature";
+pub const DEF_BEGIN_FRAME: &str = r"\begin{frame}";
+pub const DEF_END_FRAME: &str = r"\end{frame}";
+pub const DEF_BEGIN_ABSTRACT: &str = r"\begin{abstract}";
+pub const DEF_END_ABSTRACT: &str = r"\end{abstract}";
+pub const DEF_KEYWORDS: &str = r"\par\medskip\noindent\textbf{Keywords:}";
+pub const DEF_HYPERSETUP: &str = r"\hypersetup";
+pub const ABSTRACT_TITLE: &str = "Abstract";
+pub const TITLE_PAGE_DESCRIPTION: &str = "Title page";
+
+// Assets
+pub const DEFAULT_ASSETS_DIRECTORY: &str = "assets";
+
+// Lists
 pub const DEF_BEGIN_ENUMERATE: &str = r"\begin{enumerate}";
 pub const DEF_END_ENUMERATE: &str = r"\end{enumerate}";
-pub const DEF_ITEM_ENUMERATE: &str = r"\item";
+pub const DEF_BEGIN_ITEMIZE: &str = r"\begin{itemize}";
+pub const DEF_END_ITEMIZE: &str = r"\end{itemize}";
+pub const DEF_BEGIN_DESCRIPTION: &str = r"\begin{description}";
+pub const DEF_END_DESCRIPTION: &str = r"\end{description}";
+pub const DEF_ITEM: &str = r"\item";
+pub const DEF_ARABIC_COUNTER: &str = r"\arabic*";
+pub const DEF_LOWER_ROMAN_COUNTER: &str = r"\roman*";
+pub const DEF_UPPER_ROMAN_COUNTER: &str = r"\Roman*";
+pub const DEF_LOWER_ALPHA_COUNTER: &str = r"\alph*";
+pub const DEF_UPPER_ALPHA_COUNTER: &str = r"\Alph*";
+pub const ENUMITEM_PACKAGE: &str = "enumitem";
+pub const ENUMITEM_LABEL_OPTION: &str = "label";
+pub const ENUMITEM_START_OPTION: &str = "start";
+pub const ENUMITEM_RESUME_OPTION: &str = "resume";
 
 // Figure
 pub const DEF_BEGIN_FIGURE: &str = r"\begin{figure}";
 pub const DEF_END_FIGURE: &str = r"\end{figure}";
 pub const DEF_INCLUDE_GRAPH: &str = r"\includegraphics";
+pub const DEF_BEGIN_SUBFIGURE: &str = r"\begin{subfigure}";
+pub const DEF_END_SUBFIGURE: &str = r"\end{subfigure}";
+pub const DEF_SUBFIGURE_ROW_SEPARATOR: &str = r"\par\medskip";
+pub const DEF_HORIZONTAL_FILL: &str = r"\hfill";
+pub const DEF_TEXT_WIDTH: &str = r"\textwidth";
+pub const DEF_LINE_WIDTH: &str = r"\linewidth";
+pub const DEF_TEXT_HEIGHT: &str = r"\textheight";
+pub const SUBCAPTION_PACKAGE: &str = "subcaption";
+pub const SUBFIGURE_ALIGNMENT: &str = "b";
+pub const SUBFIGURES_TOTAL_WIDTH: f64 = 0.96;
 
 // Table
 pub const DEF_BEGIN_TABULAR: &str = r"\begin{tabular}";
@@ -32,6 +81,42 @@ pub const DEF_END_TABULAR: &str = r"\end{tabular}";
 pub const DEF_BEGIN_TABLE: &str = r"\begin{table}";
 pub const DEF_END_TABLE: &str = r"\end{table}";
 pub const DEF_HORIZONTAL_LINE: &str = r"\hline";
+pub const DEF_MULTICOLUMN: &str = r"\multicolumn";
+pub const DEF_MULTIROW: &str = r"\multirow";
+pub const MULTIROW_PACKAGE: &str = "multirow";
+pub const MULTIROW_NATURAL_WIDTH: &str = "*";
+pub const DEF_VERTICAL_RULE: &str = "|";
+pub const ARRAY_PACKAGE: &str = "array";
+pub const SIUNITX_PACKAGE: &str = "siunitx";
+pub const SIUNITX_COLUMN: &str = "S";
+pub const DEF_PARTIAL_LINE: &str = r"\cline";
+pub const DEF_TOP_RULE: &str = r"\toprule";
+pub const DEF_MID_RULE: &str = r"\midrule";
+pub const DEF_BOTTOM_RULE: &str = r"\bottomrule";
+pub const DEF_PARTIAL_RULE: &str = r"\cmidrule";
+pub const DEF_LINE_SPACE: &str = r"\addlinespace";
+pub const BOOKTABS_PACKAGE: &str = "booktabs";
+
+// Code listing
+pub const DEF_BEGIN_VERBATIM: &str = r"\begin{verbatim}";
+pub const DEF_END_VERBATIM: &str = r"\end{verbatim}";
+pub const DEF_BEGIN_LSTLISTING: &str = r"\begin{lstlisting}";
+pub const DEF_END_LSTLISTING: &str = r"\end{lstlisting}";
+pub const DEF_BEGIN_MINTED: &str = r"\begin{minted}";
+pub const DEF_END_MINTED: &str = r"\end{minted}";
+pub const DEF_BEGIN_LISTING: &str = r"\begin{listing}";
+pub const DEF_END_LISTING: &str = r"\end{listing}";
+pub const DEF_HIGHLIGHT_BOX: &str = r"\makebox[0pt][l]";
+pub const DEF_HIGHLIGHT_RULE: &str = r"\rule[-0.3\baselineskip]{\linewidth}{\baselineskip}";
+pub const LISTINGS_PACKAGE: &str = "listings";
+pub const MINTED_PACKAGE: &str = "minted";
+pub const LISTINGS_ESCAPE_BEGIN: &str = "(*@";
+pub const LISTINGS_ESCAPE_END: &str = "@*)";
+pub const LISTINGS_BASIC_STYLE: &str = r"basicstyle=\ttfamily";
+pub const LISTINGS_LINE_NUMBERS: &str = "numbers=left";
+pub const MINTED_LINE_NUMBERS: &str = "linenos";
+pub const MINTED_PLAIN_LANGUAGE: &str = "text";
+pub const DEFAULT_HIGHLIGHT_COLOR: &str = "yellow!30";
 
 // Section
 pub const DEF_SECTION: &str = r"\section";
@@ -44,16 +129,99 @@ pub const DEF_CHAPTER: &str = r"\chapter";
 // Page Break
 pub const DEF_NEW_PAGE: &str = r"\newpage";
 
+// Contents
+pub const DEF_TABLE_OF_CONTENTS: &str = r"\tableofcontents";
+pub const DEF_LIST_OF_FIGURES: &str = r"\listoffigures";
+pub const DEF_LIST_OF_TABLES: &str = r"\listoftables";
+pub const DEF_CONTENTS_NAME: &str = r"\contentsname";
+pub const DEF_LIST_FIGURE_NAME: &str = r"\listfigurename";
+pub const DEF_LIST_TABLE_NAME: &str = r"\listtablename";
+pub const DEF_RENEW_COMMAND: &str = r"\renewcommand";
+pub const DEF_SET_COUNTER: &str = r"\setcounter";
+pub const DEF_ADD_CONTENTS_LINE: &str = r"\addcontentsline";
+pub const DEF_PHANTOM_SECTION: &str = r"\phantomsection";
+pub const TOC_DEPTH_COUNTER: &str = "tocdepth";
+pub const TOC_FILE: &str = "toc";
+pub const MAX_TOC_DEPTH: usize = 5;
+
 // Caption
 pub const DEF_CAPTION: &str = r"\caption";
 
 // Label
 pub const DEF_LABEL: &str = r"\label";
 
+// References
+pub const DEF_REF: &str = r"\ref";
+pub const DEF_PAGE_REF: &str = r"\pageref";
+pub const DEF_EQ_REF: &str = r"\eqref";
+pub const DEF_AUTO_REF: &str = r"\autoref";
+pub const MARKUP_REF: &str = "ref";
+pub const MARKUP_PAGE_REF: &str = "pageref";
+pub const MARKUP_EQ_REF: &str = "eqref";
+pub const MARKUP_AUTO_REF: &str = "autoref";
+pub const AMSMATH_PACKAGE: &str = "amsmath";
+pub const HYPERREF_PACKAGE: &str = "hyperref";
+
+// Math
+pub const DEF_BEGIN_EQUATION: &str = r"\begin{equation}";
+pub const DEF_END_EQUATION: &str = r"\end{equation}";
+pub const DEF_BEGIN_UNNUMBERED_EQUATION: &str = r"\begin{equation*}";
+pub const DEF_END_UNNUMBERED_EQUATION: &str = r"\end{equation*}";
+pub const DEF_BEGIN_ALIGN: &str = r"\begin{align}";
+pub const DEF_END_ALIGN: &str = r"\end{align}";
+pub const DEF_BEGIN_UNNUMBERED_ALIGN: &str = r"\begin{align*}";
+pub const DEF_END_UNNUMBERED_ALIGN: &str = r"\end{align*}";
+pub const DEF_BEGIN_GATHER: &str = r"\begin{gather}";
+pub const DEF_END_GATHER: &str = r"\end{gather}";
+pub const DEF_BEGIN_UNNUMBERED_GATHER: &str = r"\begin{gather*}";
+pub const DEF_END_UNNUMBERED_GATHER: &str = r"\end{gather*}";
+pub const DEF_BEGIN: &str = r"\begin";
+pub const DEF_END: &str = r"\end";
+pub const AMSMATH_MATH_COMMANDS: [&str; 6] = [r"\operatorname", r"matrix}", r"\text{", r"\dfrac", r"\tfrac", r"\binom"];
+
+// Math expressions
+pub const DEF_FRACTION: &str = r"\frac";
+pub const DEF_SQUARE_ROOT: &str = r"\sqrt";
+pub const DEF_SUM: &str = r"\sum";
+pub const DEF_PRODUCT: &str = r"\prod";
+pub const DEF_INTEGRAL: &str = r"\int";
+pub const DEF_DIFFERENTIAL: &str = r"\,d";
+pub const DEF_CDOT: &str = r"\cdot";
+pub const DEF_TIMES: &str = r"\times";
+pub const DEF_PLUS_MINUS: &str = r"\pm";
+pub const DEF_NOT_EQUALS: &str = r"\neq";
+pub const DEF_LESS_OR_EQUAL: &str = r"\leq";
+pub const DEF_GREATER_OR_EQUAL: &str = r"\geq";
+pub const DEF_APPROX: &str = r"\approx";
+pub const DEF_INFINITY: &str = r"\infty";
+pub const DEF_LEFT_PARENTHESIS: &str = r"\left(";
+pub const DEF_RIGHT_PARENTHESIS: &str = r"\right)";
+pub const DEF_MATH_ITALIC: &str = r"\mathit";
+pub const DEF_MATH_TEXT: &str = r"\text";
+pub const NOT_A_NUMBER: &str = "NaN";
+pub const POSITIVE_INFINITY: &str = "inf";
+pub const DEF_OPERATOR_NAME: &str = r"\operatorname";
+pub const DEF_MATH_TILDE: &str = r"\sim{}";
+pub const DEF_MATH_CIRCUMFLEX: &str = r"\wedge{}";
+pub const DEF_MATH_BACKSLASH: &str = r"\backslash{}";
+pub const MATRIX_ENVIRONMENT: &str = "matrix";
+pub const PARENTHESES_MATRIX_ENVIRONMENT: &str = "pmatrix";
+pub const BRACKETS_MATRIX_ENVIRONMENT: &str = "bmatrix";
+pub const BRACES_MATRIX_ENVIRONMENT: &str = "Bmatrix";
+pub const BARS_MATRIX_ENVIRONMENT: &str = "vmatrix";
+pub const DOUBLE_BARS_MATRIX_ENVIRONMENT: &str = "Vmatrix";
+
 // Positioning
 pub const DEF_CENTERING: &str = r"\centering";
 
 // Formatting
 pub const DEF_BOLD: &str = r"\textbf";
 pub const DEF_ITALIC: &str = r"\textit";
-pub const DEF_COLOR: &str = r"\color";
\ No newline at end of file
+pub const DEF_COLOR: &str = r"\color";
+pub const DEF_MONOSPACE: &str = r"\texttt";
+pub const DEF_LINE_BREAK: &str = r"\\";
+
+// Escaping
+pub const DEF_TILDE: &str = r"\textasciitilde{}";
+pub const DEF_CIRCUMFLEX: &str = r"\textasciicircum{}";
+pub const DEF_BACKSLASH: &str = r"\textbackslash{}";
\ No newline at end of file
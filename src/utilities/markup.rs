@@ -0,0 +1,720 @@
+// Parser for the inline markup language of `Text`
+//
+// Grammar:
+// - `**text**`          bold
+// - `_text_`            italic (not inside words, e.g. `snake_case`)
+// - `#color{text}`      colored text
+// - `$x_1 * y$`         inline math, written as is (other `$`, e.g. in `$5 and $10`, are literal)
+// - `` `code` ``        inline code, escaped but not formatted (``` `` a`b `` ``` to include backticks)
+// - `@ref{label}`       reference (also `@pageref`, `@eqref` and `@autoref`)
+// - `\*`, `\_`, `\#`, `\@`, `\$`, `\``, `\{`, `\}`, `\\`   literal characters
+// - `\\` or two spaces at the end of a line    line break (dropped at the end of a paragraph)
+//
+// Markup can be nested, e.g. `**bold _italic_**` or `#red{**x**}`.
+
+use std::fmt;
+use super::def_syntax::*;
+use super::format::*;
+use crate::components::package::*;
+use crate::components::reference::*;
+
+const BOLD_MARKER: &str = "**";
+const ITALIC_MARKER: &str = "_";
+const COLOR_MARKER: &str = "#";
+const MATH_MARKER: &str = "$";
+const CODE_MARKER: char = '`';
+const ESCAPABLE_CHARS: [char; 9] = ['*', '_', '#', '@', '$', '`', '{', '}', '\\'];
+
+/// The type of a reference to a `Label`.
+#[derive(Clone, Copy)]
+pub enum ReferenceType {
+    Ref,
+    PageRef,
+    EqRef,
+    AutoRef
+}
+
+impl ReferenceType {
+    fn from_markup(name: &str) -> Option<Self> {
+        match name {
+            MARKUP_REF => Some(ReferenceType::Ref),
+            MARKUP_PAGE_REF => Some(ReferenceType::PageRef),
+            MARKUP_EQ_REF => Some(ReferenceType::EqRef),
+            MARKUP_AUTO_REF => Some(ReferenceType::AutoRef),
+            _ => None
+        }
+    }
+
+    fn get_def(&self) -> &str {
+        match self {
+            ReferenceType::Ref => DEF_REF,
+            ReferenceType::PageRef => DEF_PAGE_REF,
+            ReferenceType::EqRef => DEF_EQ_REF,
+            ReferenceType::AutoRef => DEF_AUTO_REF
+        }
+    }
+
+    fn required_packages(&self) -> Vec<Package> {
+        match self {
+            ReferenceType::EqRef => vec![Package::new(AMSMATH_PACKAGE, vec![])],
+            ReferenceType::AutoRef => vec![Package::new(HYPERREF_PACKAGE, vec![])],
+            _ => Vec::new()
+        }
+    }
+}
+
+/// A node of the inline AST of a `Text`.
+pub enum Inline {
+    Plain(String),
+    Raw(String),
+    Bold(Vec<Inline>),
+    Italic(Vec<Inline>),
+    Color(String, Vec<Inline>),
+    Math(String),
+    Code(String),
+    LineBreak,
+    Reference(ReferenceType, Label)
+}
+
+impl Inline {
+    pub fn to_latex(&self) -> String {
+        match self {
+            Inline::Plain(text) => escape_text(text),
+            Inline::Raw(text) => text.clone(),
+            Inline::Bold(children) => into_bold(&render_inlines(children)),
+            Inline::Italic(children) => into_italic(&render_inlines(children)),
+            Inline::Color(color, children) => into_color(&render_inlines(children), color),
+            Inline::Math(expression) => format!("{}{}{}", MATH_MARKER, expression, MATH_MARKER),
+            Inline::Code(code) => into_monospace(&escape_text(code)),
+            Inline::LineBreak => format!(" {}", DEF_LINE_BREAK),
+            Inline::Reference(reference_type, label) => {
+                format!("{}{}", reference_type.get_def(), into_braces(&String::from(label.get_name())))
+            }
+        }
+    }
+
+    pub fn required_packages(&self) -> Vec<Package> {
+        match self {
+            Inline::Plain(_) | Inline::Raw(_) | Inline::Code(_) | Inline::LineBreak => Vec::new(),
+            Inline::Bold(children) | Inline::Italic(children) => inlines_required_packages(children),
+            Inline::Color(_, children) => {
+                let mut packages = inlines_required_packages(children);
+                packages.push(Package::new(XCOLOR_PACKAGE, vec![]));
+                packages
+            }
+            Inline::Math(expression) => {
+                match AMSMATH_MATH_COMMANDS.iter().any(|command| expression.contains(command)) {
+                    true => vec![Package::new(AMSMATH_PACKAGE, vec![])],
+                    false => Vec::new()
+                }
+            }
+            Inline::Reference(reference_type, _) => reference_type.required_packages()
+        }
+    }
+
+    pub fn contains_line_break(&self) -> bool {
+        match self {
+            Inline::LineBreak => true,
+            Inline::Bold(children) | Inline::Italic(children) | Inline::Color(_, children) => {
+                inlines_contain_line_break(children)
+            }
+            _ => false
+        }
+    }
+
+    pub fn references(&self) -> Vec<Label> {
+        match self {
+            Inline::Plain(_) | Inline::Raw(_) | Inline::Math(_) | Inline::Code(_) | Inline::LineBreak => Vec::new(),
+            Inline::Bold(children) | Inline::Italic(children) | Inline::Color(_, children) => {
+                inlines_references(children)
+            }
+            Inline::Reference(_, label) => vec![label.clone()]
+        }
+    }
+}
+
+pub fn inlines_references(inlines: &[Inline]) -> Vec<Label> {
+    inlines
+        .iter()
+        .flat_map(|inline| inline.references())
+        .collect()
+}
+
+pub fn inlines_contain_line_break(inlines: &[Inline]) -> bool {
+    inlines.iter().any(|inline| inline.contains_line_break())
+}
+
+pub fn inlines_required_packages(inlines: &[Inline]) -> Vec<Package> {
+    inlines
+        .iter()
+        .flat_map(|inline| inline.required_packages())
+        .collect()
+}
+
+pub fn render_inlines(inlines: &[Inline]) -> String {
+    inlines
+        .iter()
+        .map(|inline| inline.to_latex())
+        .collect()
+}
+
+/// An error raised by unbalanced markup, at a line and column of the text, both starting at 1.
+#[derive(Clone, Debug)]
+pub struct MarkupError {
+    message: String,
+    line: usize,
+    column: usize
+}
+
+impl fmt::Display for MarkupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} at line {}, column {}", self.message, self.line, self.column)
+    }
+}
+
+enum Delimiter {
+    Bold,
+    Italic,
+    Color(String)
+}
+
+impl Delimiter {
+    fn marker(&self) -> String {
+        match self {
+            Delimiter::Bold => String::from(BOLD_MARKER),
+            Delimiter::Italic => String::from(ITALIC_MARKER),
+            Delimiter::Color(color) => format!("{}{}{{", COLOR_MARKER, color)
+        }
+    }
+
+    fn into_inline(self, children: Vec<Inline>) -> Inline {
+        match self {
+            Delimiter::Bold => Inline::Bold(children),
+            Delimiter::Italic => Inline::Italic(children),
+            Delimiter::Color(color) => Inline::Color(color, children)
+        }
+    }
+}
+
+struct Frame {
+    delimiter: Delimiter,
+    children: Vec<Inline>,
+    start: usize,
+    brace_depth: usize
+}
+
+struct Parser {
+    chars: Vec<char>,
+    position: usize,
+    root: Vec<Inline>,
+    frames: Vec<Frame>,
+    plain: String
+}
+
+/// Parses a markup string into its inline AST.
+/// Windows line endings (`\r\n`) are read as `\n`.
+pub fn parse_markup(text: &str) -> Result<Vec<Inline>, MarkupError> {
+    let parser = Parser {
+        chars: text.replace("\r\n", "\n").chars().collect(),
+        position: 0,
+        root: Vec::new(),
+        frames: Vec::new(),
+        plain: String::new()
+    };
+
+    parser.parse()
+}
+
+impl Parser {
+    fn parse(mut self) -> Result<Vec<Inline>, MarkupError> {
+        while let Some(character) = self.current() {
+            match character {
+                '\\' | ' ' if self.is_line_break() => self.parse_line_break(),
+                '\\' => self.parse_escape(),
+                '*' if self.peek(1) == Some('*') => self.parse_bold()?,
+                '_' => self.parse_italic()?,
+                '#' => self.parse_color(),
+                '@' => self.parse_reference(),
+                '$' => self.parse_math(),
+                '`' => self.parse_code()?,
+                '{' => self.parse_open_brace(),
+                '}' => self.parse_close_brace()?,
+                _ => self.push_char(character)
+            }
+        }
+
+        if let Some(frame) = self.frames.last() {
+            return Err(self.error(
+                format!("unclosed '{}'", frame.delimiter.marker()),
+                frame.start
+            ));
+        }
+
+        self.flush_plain();
+        Ok(self.root)
+    }
+
+    // A line ends with `\\` or with at least two spaces, followed by a newline.
+    // Trailing spaces do not break a blank line
+    fn is_line_break(&self) -> bool {
+        let rest = &self.chars[self.position..];
+        let marker_length = match rest {
+            ['\\', '\\', ..] => 2,
+            [' ', ' ', ..] if !self.is_line_blank() => 0,
+            _ => return false
+        };
+
+        rest[marker_length..]
+            .iter()
+            .find(|character| **character != ' ' && **character != '\t')
+            .is_some_and(|character| *character == '\n')
+    }
+
+    // The newline is kept, so the line structure of the text is preserved.
+    // A line break before a blank line or at the end of the text has no line to end, so it is dropped
+    fn parse_line_break(&mut self) {
+        while self.current().is_some_and(|character| character != '\n') {
+            self.position += 1;
+        }
+
+        let trimmed_plain = String::from(self.plain.trim_end());
+        self.plain = trimmed_plain;
+
+        if !self.ends_paragraph() {
+            self.flush_plain();
+            self.current_children().push(Inline::LineBreak);
+        }
+    }
+
+    // Only whitespace precedes the current position on its line
+    fn is_line_blank(&self) -> bool {
+        self.chars[..self.position]
+            .iter()
+            .rev()
+            .find(|character| !matches!(character, ' ' | '\t'))
+            .is_none_or(|character| *character == '\n')
+    }
+
+    // The newline at the current position is followed by a blank line or by the end of the text
+    fn ends_paragraph(&self) -> bool {
+        self.chars[self.position + 1..]
+            .iter()
+            .find(|character| !matches!(character, ' ' | '\t'))
+            .is_none_or(|character| *character == '\n')
+    }
+
+    fn parse_escape(&mut self) {
+        match self.peek(1) {
+            Some(escaped) if ESCAPABLE_CHARS.contains(&escaped) => {
+                self.plain.push(escaped);
+                self.position += 2;
+            }
+            _ => self.push_char('\\')
+        }
+    }
+
+    fn parse_bold(&mut self) -> Result<(), MarkupError> {
+        let start = self.position;
+        self.position += BOLD_MARKER.len();
+
+        if self.is_open(|delimiter| matches!(delimiter, Delimiter::Bold)) {
+            self.close_frame(start, |delimiter| matches!(delimiter, Delimiter::Bold))
+        } else {
+            self.open_frame(Delimiter::Bold, start);
+            Ok(())
+        }
+    }
+
+    fn parse_italic(&mut self) -> Result<(), MarkupError> {
+        let start = self.position;
+        let is_italic_open = self.is_open(|delimiter| matches!(delimiter, Delimiter::Italic));
+
+        if is_italic_open && self.can_close_italic() {
+            self.position += ITALIC_MARKER.len();
+            self.close_frame(start, |delimiter| matches!(delimiter, Delimiter::Italic))
+        } else if self.can_open_italic() {
+            self.position += ITALIC_MARKER.len();
+            self.open_frame(Delimiter::Italic, start);
+            Ok(())
+        } else {
+            self.push_char('_');
+            Ok(())
+        }
+    }
+
+    fn parse_color(&mut self) {
+        let start = self.position;
+        let color: String = self.chars[start + 1..]
+            .iter()
+            .take_while(|character| character.is_ascii_alphanumeric() || "!.-".contains(**character))
+            .collect();
+
+        let brace_position = start + 1 + color.chars().count();
+
+        if !color.is_empty() && self.chars.get(brace_position) == Some(&'{') {
+            self.position = brace_position + 1;
+            self.open_frame(Delimiter::Color(color), start);
+        } else {
+            self.push_char('#');
+        }
+    }
+
+    fn parse_reference(&mut self) {
+        let start = self.position;
+        let name: String = self.chars[start + 1..]
+            .iter()
+            .take_while(|character| character.is_ascii_lowercase())
+            .collect();
+
+        let brace_position = start + 1 + name.len();
+        let label_end = self.chars[brace_position..]
+            .iter()
+            .position(|character| *character == '}')
+            .map(|offset| brace_position + offset);
+
+        match (ReferenceType::from_markup(&name), self.chars.get(brace_position), label_end) {
+            (Some(reference_type), Some('{'), Some(label_end)) => {
+                let label: String = self.chars[brace_position + 1..label_end].iter().collect();
+                self.flush_plain();
+                self.current_children().push(Inline::Reference(reference_type, Label::new(&label)));
+                self.position = label_end + 1;
+            }
+            _ => self.push_char('@')
+        }
+    }
+
+    // The content of inline math is kept as is, so `_` and `*` are not formatted.
+    // Like in Markdown, `$` opens math before a non-space character and closes it after one,
+    // unless it is followed by a digit, so amounts such as `$5 and $10` stay literal.
+    fn parse_math(&mut self) {
+        let start = self.position;
+
+        if self.peek(1).is_none_or(|character| character.is_whitespace()) {
+            self.push_char('$');
+            return;
+        }
+
+        let mut end = start + 1;
+
+        while end < self.chars.len() && !self.can_close_math(end) {
+            end += match self.chars[end] {
+                '\\' => 2,
+                _ => 1
+            };
+        }
+
+        if end >= self.chars.len() {
+            self.push_char('$');
+            return;
+        }
+
+        let expression: String = self.chars[start + 1..end].iter().collect();
+        self.flush_plain();
+        self.current_children().push(Inline::Math(expression));
+        self.position = end + 1;
+    }
+
+    fn can_close_math(&self, position: usize) -> bool {
+        self.chars[position] == '$'
+            && !self.chars[position - 1].is_whitespace()
+            && !self.chars.get(position + 1).is_some_and(|character| character.is_ascii_digit())
+    }
+
+    // Inline code is opened by a run of backticks and closed by a run of the same length,
+    // so ``` `` a`b `` ``` contains a backtick. Its content is not formatted.
+    fn parse_code(&mut self) -> Result<(), MarkupError> {
+        let start = self.position;
+        let marker_length = self.count_backticks(start);
+        let mut end = start + marker_length;
+
+        loop {
+            if end >= self.chars.len() {
+                return Err(self.error(format!("unclosed '{}'", CODE_MARKER.to_string().repeat(marker_length)), start));
+            }
+
+            match self.chars[end] {
+                CODE_MARKER => {
+                    let run_length = self.count_backticks(end);
+
+                    if run_length == marker_length {
+                        break;
+                    }
+
+                    end += run_length;
+                }
+                _ => end += 1
+            }
+        }
+
+        let mut code: String = self.chars[start + marker_length..end].iter().collect();
+
+        if marker_length > 1 && code.starts_with(' ') && code.ends_with(' ') && !code.trim().is_empty() {
+            code = String::from(&code[1..code.len() - 1]);
+        }
+
+        self.flush_plain();
+        self.current_children().push(Inline::Code(code));
+        self.position = end + marker_length;
+        Ok(())
+    }
+
+    fn count_backticks(&self, position: usize) -> usize {
+        self.chars[position..]
+            .iter()
+            .take_while(|character| **character == CODE_MARKER)
+            .count()
+    }
+
+    fn parse_open_brace(&mut self) {
+        if let Some(frame) = self.frames.last_mut() {
+            if let Delimiter::Color(_) = frame.delimiter {
+                frame.brace_depth += 1;
+            }
+        }
+
+        self.push_char('{');
+    }
+
+    fn parse_close_brace(&mut self) -> Result<(), MarkupError> {
+        let start = self.position;
+
+        if let Some(frame) = self.frames.last_mut() {
+            if let Delimiter::Color(_) = frame.delimiter {
+                if frame.brace_depth > 0 {
+                    frame.brace_depth -= 1;
+                    self.push_char('}');
+                    return Ok(());
+                }
+            }
+        }
+
+        if self.is_open(|delimiter| matches!(delimiter, Delimiter::Color(_))) {
+            self.position += 1;
+            self.close_frame(start, |delimiter| matches!(delimiter, Delimiter::Color(_)))
+        } else {
+            self.push_char('}');
+            Ok(())
+        }
+    }
+
+    fn open_frame(&mut self, delimiter: Delimiter, start: usize) {
+        self.flush_plain();
+        self.frames.push(Frame {
+            delimiter,
+            children: Vec::new(),
+            start,
+            brace_depth: 0
+        });
+    }
+
+    fn close_frame<F: Fn(&Delimiter) -> bool>(&mut self, start: usize, is_closed: F) -> Result<(), MarkupError> {
+        let top = self.frames.last().unwrap();
+
+        if !is_closed(&top.delimiter) {
+            let closing = self.chars[start..self.position].iter().collect::<String>();
+            return Err(self.error(
+                format!("'{}' closes a markup while '{}' is still open", closing, top.delimiter.marker()),
+                start
+            ));
+        }
+
+        self.flush_plain();
+        let frame = self.frames.pop().unwrap();
+        let inline = frame.delimiter.into_inline(frame.children);
+        self.current_children().push(inline);
+        Ok(())
+    }
+
+    fn is_open<F: Fn(&Delimiter) -> bool>(&self, predicate: F) -> bool {
+        self.frames.iter().any(|frame| predicate(&frame.delimiter))
+    }
+
+    // An italic marker opens at the start of a word and closes at its end,
+    // so underscores inside identifiers such as `snake_case_name` stay literal.
+    fn can_open_italic(&self) -> bool {
+        let previous = self.peek_back(1);
+        let next = self.peek(1);
+
+        !previous.is_some_and(|character| character.is_alphanumeric())
+            && next.is_some_and(|character| !character.is_whitespace())
+    }
+
+    fn can_close_italic(&self) -> bool {
+        let previous = self.peek_back(1);
+        let next = self.peek(1);
+
+        previous.is_some_and(|character| !character.is_whitespace())
+            && !next.is_some_and(|character| character.is_alphanumeric())
+    }
+
+    fn current(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn peek(&self, offset: usize) -> Option<char> {
+        self.chars.get(self.position + offset).copied()
+    }
+
+    fn peek_back(&self, offset: usize) -> Option<char> {
+        self.position
+            .checked_sub(offset)
+            .and_then(|position| self.chars.get(position).copied())
+    }
+
+    fn push_char(&mut self, character: char) {
+        self.plain.push(character);
+        self.position += 1;
+    }
+
+    fn flush_plain(&mut self) {
+        if !self.plain.is_empty() {
+            let plain = std::mem::take(&mut self.plain);
+            self.current_children().push(Inline::Plain(plain));
+        }
+    }
+
+    fn current_children(&mut self) -> &mut Vec<Inline> {
+        match self.frames.last_mut() {
+            Some(frame) => &mut frame.children,
+            None => &mut self.root
+        }
+    }
+
+    fn error(&self, message: String, position: usize) -> MarkupError {
+        let preceding_chars = &self.chars[..position];
+        let line_start = preceding_chars
+            .iter()
+            .rposition(|character| *character == '\n')
+            .map_or(0, |index| index + 1);
+
+        MarkupError {
+            message,
+            line: preceding_chars.iter().filter(|character| **character == '\n').count() + 1,
+            column: position - line_start + 1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render(text: &str) -> String {
+        render_inlines(&parse_markup(text).unwrap())
+    }
+
+    fn parse_error(text: &str) -> String {
+        match parse_markup(text) {
+            Ok(inlines) => panic!("'{}' was parsed as '{}'", text, render_inlines(&inlines)),
+            Err(error) => error.to_string()
+        }
+    }
+
+    #[test]
+    fn parses_nested_markup() {
+        assert_eq!(render("**_x_**"), r"\textbf{\textit{x}}");
+        assert_eq!(render("_**x**_"), r"\textit{\textbf{x}}");
+        assert_eq!(render("**bold _italic_ bold**"), r"\textbf{bold \textit{italic} bold}");
+        assert_eq!(render("#red{**x** and _y_}"), r"{\color{red}{\textbf{x} and \textit{y}}}");
+        assert_eq!(render("#red{a {b} c}"), r"{\color{red}{a \{b\} c}}");
+    }
+
+    #[test]
+    fn keeps_underscores_inside_words() {
+        assert_eq!(render("user_id"), r"user\_id");
+        assert_eq!(render("snake_case_name and _italic_"), r"snake\_case\_name and \textit{italic}");
+        assert_eq!(render("_user_id_"), r"\textit{user\_id}");
+        assert_eq!(render("a _ b"), r"a \_ b");
+        assert_eq!(render("2 * 3"), "2 * 3");
+    }
+
+    #[test]
+    fn reports_unclosed_markup() {
+        assert_eq!(parse_error("**bold"), "unclosed '**' at line 1, column 1");
+        assert_eq!(parse_error("some _italic"), "unclosed '_' at line 1, column 6");
+        assert_eq!(parse_error("#red{text"), "unclosed '#red{' at line 1, column 1");
+        assert_eq!(parse_error("`code"), "unclosed '`' at line 1, column 1");
+        assert_eq!(parse_error("**bold _italic**_"), "'**' closes a markup while '_' is still open at line 1, column 15");
+        assert_eq!(parse_error("first line\r\nsecond **line"), "unclosed '**' at line 2, column 8");
+    }
+
+    #[test]
+    fn parses_escapes() {
+        let escapes = [
+            ('*', "*"), ('_', r"\_"), ('#', r"\#"), ('@', "@"), ('$', r"\$"),
+            ('`', "`"), ('{', r"\{"), ('}', r"\}"), ('\\', r"\textbackslash{}")
+        ];
+
+        assert_eq!(escapes.map(|(character, _)| character), ESCAPABLE_CHARS);
+
+        for (character, expected) in escapes {
+            assert_eq!(render(&format!("\\{}", character)), expected, "escape of '{}'", character);
+        }
+
+        assert_eq!(render(r"\*\*not bold\*\*"), "**not bold**");
+        assert_eq!(render(r"\_not italic\_"), r"\_not italic\_");
+        assert_eq!(render(r"\#red{x}"), r"\#red\{x\}");
+        assert_eq!(render(r"\n"), r"\textbackslash{}n");
+    }
+
+    #[test]
+    fn parses_references() {
+        let inlines = parse_markup("See @ref{fig_1}, @pageref{fig_1}, @eqref{eq_1} and @autoref{tab_1}.").unwrap();
+
+        assert_eq!(render_inlines(&inlines), r"See \ref{fig_1}, \pageref{fig_1}, \eqref{eq_1} and \autoref{tab_1}.");
+        assert_eq!(inlines_references(&inlines), vec![Label::new("fig_1"), Label::new("fig_1"),
+                                                      Label::new("eq_1"), Label::new("tab_1")]);
+
+        let packages: Vec<String> = inlines_required_packages(&inlines)
+            .iter()
+            .map(|package| String::from(package.get_name()))
+            .collect();
+        assert_eq!(packages, vec![AMSMATH_PACKAGE, HYPERREF_PACKAGE]);
+    }
+
+    #[test]
+    fn keeps_unknown_references_literal() {
+        assert_eq!(render("mail@example.com"), "mail@example.com");
+        assert_eq!(render("@unknown{x}"), r"@unknown\{x\}");
+        assert_eq!(render("@ref without braces"), "@ref without braces");
+        assert_eq!(render(r"\@ref{fig_1}"), r"@ref\{fig\_1\}");
+        assert_eq!(render("**@ref{fig_1}**"), r"\textbf{\ref{fig_1}}");
+    }
+
+    #[test]
+    fn parses_inline_math() {
+        assert_eq!(render("$x_1 * y$ and $a$"), "$x_1 * y$ and $a$");
+        assert_eq!(render(r"$\frac{a}{b}$"), r"$\frac{a}{b}$");
+        assert_eq!(render(r"$a \$ b$"), r"$a \$ b$");
+    }
+
+    #[test]
+    fn keeps_unmatched_dollars_literal() {
+        assert_eq!(render("costs $5"), r"costs \$5");
+        assert_eq!(render("$5 and $10"), r"\$5 and \$10");
+        assert_eq!(render("from $5 to $10 per_unit"), r"from \$5 to \$10 per\_unit");
+        assert_eq!(render("a $ b $ c"), r"a \$ b \$ c");
+        assert_eq!(render("ends with $"), r"ends with \$");
+        assert_eq!(render("$x$ costs $5"), r"$x$ costs \$5");
+    }
+
+    #[test]
+    fn parses_line_breaks() {
+        assert_eq!(render("first\\\\\nsecond"), "first \\\\\nsecond");
+        assert_eq!(render("first  \nsecond"), "first \\\\\nsecond");
+        assert_eq!(render("**first  \nsecond**"), "\\textbf{first \\\\\nsecond}");
+        assert_eq!(render("a \\\\ b"), r"a \textbackslash{} b");
+        assert_eq!(render("first  \r\nsecond\\\\\r\nthird"), "first \\\\\nsecond \\\\\nthird");
+        assert_eq!(render("first  \r\n\r\nsecond"), "first\n\nsecond");
+    }
+
+    #[test]
+    fn drops_line_breaks_ending_a_paragraph() {
+        assert_eq!(render("first  \n\nsecond"), "first\n\nsecond");
+        assert_eq!(render("first\\\\\n   \nsecond"), "first\n   \nsecond");
+        assert_eq!(render("last line  \n"), "last line\n");
+        assert_eq!(render("first\n   \nsecond"), "first\n   \nsecond");
+        assert!(!inlines_contain_line_break(&parse_markup("first  \n\nsecond").unwrap()));
+        assert!(inlines_contain_line_break(&parse_markup("_first  \nsecond_").unwrap()));
+    }
+}
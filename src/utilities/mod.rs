@@ -1,2 +1,3 @@
 pub mod format;
-pub mod def_syntax;
\ No newline at end of file
+pub mod def_syntax;
+pub mod markup;
\ No newline at end of file